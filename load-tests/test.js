@@ -1,14 +1,33 @@
 import {check} from 'k6';
 import http from 'k6/http';
 
+const BASE_URL = 'http://host.docker.internal:8888';
+
 export const options = {
     vus: 2000,
     duration: '20s',
 };
 
-export default function () {
-    const res = http.get('http://host.docker.internal:8888/questions/10');
+// Question ids are allocated by the server, so create one to read before the test starts.
+export function setup() {
+    const res = http.post(
+        `${BASE_URL}/questions`,
+        JSON.stringify({
+            title: 'How fast is GET /questions/:id?',
+            content: 'Question created by the k6 load test.',
+            tags: ['load-test'],
+        }),
+        {headers: {'Content-Type': 'application/json'}},
+    );
+    check(res, {
+        'is status 201': (r) => r.status === 201,
+    });
+    return {questionId: res.json('id')};
+}
+
+export default function (data) {
+    const res = http.get(`${BASE_URL}/questions/${data.questionId}`);
     check(res, {
         'is status 200': (r) => r.status === 200,
     });
-}
\ No newline at end of file
+}
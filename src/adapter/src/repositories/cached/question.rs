@@ -0,0 +1,285 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use tracing::warn;
+use uuid::Uuid;
+
+use rust_core::common::errors::CoreError;
+use rust_core::common::typed_cache::TypedCache;
+use rust_core::entities::page_entity::PageEntity;
+use rust_core::entities::question::{CreateQuestionEntity, QuestionEntity, QuestionId};
+use rust_core::entities::question_filter::QuestionFilter;
+use rust_core::ports::cache::CachePort;
+use rust_core::ports::question::QuestionPort;
+
+/// Represents question cache configuration.
+#[derive(Debug, Clone, Deserialize)]
+pub struct QuestionCacheConfig {
+    /// Seconds a question stays cached.
+    #[serde(default = "default_question_secs")]
+    pub question_secs: u64,
+    /// Seconds a page or count of questions stays cached.
+    #[serde(default = "default_list_secs")]
+    pub list_secs: u64,
+}
+
+fn default_question_secs() -> u64 {
+    60
+}
+
+fn default_list_secs() -> u64 {
+    10
+}
+
+impl Default for QuestionCacheConfig {
+    fn default() -> Self {
+        Self {
+            question_secs: default_question_secs(),
+            list_secs: default_list_secs(),
+        }
+    }
+}
+
+/// Caches the reads of a `QuestionPort` in a `CachePort`.
+///
+/// `get` reads through a per question entry, which every write through this repository
+/// replaces, remembering deleted and missing questions as `None`. A read only fills the entry when
+/// it is absent, so it cannot overwrite what a concurrent write stored, and a failed write drops
+/// the entry in case it was stale. Pages and counts are cached under a SHA-256 digest of their
+/// filter and a generation that every write replaces, so a write makes all of them unreachable at
+/// once; they are left to expire. Writes made around this repository, such as by another replica
+/// without the same cache, show up once the entries expire.
+pub struct CachedQuestionRepository<C> {
+    question_port: Arc<dyn QuestionPort + Send + Sync>,
+    questions: TypedCache<Option<QuestionEntity>, C>,
+    pages: TypedCache<PageEntity<QuestionEntity>, C>,
+    counts: TypedCache<usize, C>,
+    generations: TypedCache<String, C>,
+    question_ttl: Duration,
+    list_ttl: Duration,
+}
+
+impl<C: CachePort + Clone + Send + Sync> CachedQuestionRepository<C> {
+    pub fn new(
+        question_port: Arc<dyn QuestionPort + Send + Sync>,
+        cache: C,
+        config: &QuestionCacheConfig,
+    ) -> Self {
+        CachedQuestionRepository {
+            question_port,
+            questions: TypedCache::new(cache.clone(), "question"),
+            pages: TypedCache::new(cache.clone(), "question-page"),
+            counts: TypedCache::new(cache.clone(), "question-count"),
+            generations: TypedCache::new(cache, "question-generation"),
+            question_ttl: Duration::from_secs(config.question_secs),
+            list_ttl: Duration::from_secs(config.list_secs),
+        }
+    }
+
+    /// Replaces the cached question, logging instead of failing when the cache is unavailable.
+    async fn remember(&self, question_id: &QuestionId, question: Option<&QuestionEntity>) {
+        let question = question.cloned();
+        let result = self
+            .questions
+            .set(&question_id.to_string(), &question, Some(self.question_ttl))
+            .await;
+        if let Err(err) = result {
+            warn!("Failed to cache question {}: {}", question_id, err);
+        }
+    }
+
+    /// Caches the question read from the inner repository, unless a write cached it meanwhile.
+    async fn fill(&self, question_id: &QuestionId, question: Option<&QuestionEntity>) {
+        let question = question.cloned();
+        let result = self
+            .questions
+            .set_if_absent(&question_id.to_string(), &question, Some(self.question_ttl))
+            .await;
+        if let Err(err) = result {
+            warn!("Failed to cache question {}: {}", question_id, err);
+        }
+    }
+
+    /// Drops the cached question after a write failed, since the failure may come from the cached
+    /// copy being stale.
+    async fn forget(&self, question_id: &QuestionId) {
+        match self.questions.del(&question_id.to_string()).await {
+            Ok(()) | Err(CoreError::NotFound) => {}
+            Err(err) => warn!("Failed to drop cached question {}: {}", question_id, err),
+        }
+    }
+
+    /// Runs a write of the inner repository, dropping the cached question when it fails.
+    async fn write<T, F>(&self, question_id: &QuestionId, write: F) -> Result<T, CoreError>
+    where
+        F: std::future::Future<Output = Result<T, CoreError>>,
+    {
+        let result = write.await;
+        if result.is_err() {
+            self.forget(question_id).await;
+        }
+        result
+    }
+
+    /// Makes every cached page and count unreachable.
+    async fn invalidate_lists(&self) {
+        let generation = Uuid::now_v7().to_string();
+        if let Err(err) = self.generations.set("current", &generation, None).await {
+            warn!("Failed to invalidate cached question lists: {}", err);
+        }
+    }
+
+    /// Key of the cached result of `kind` for `question_filter` in the current generation, or
+    /// `None` when the cache cannot be used.
+    async fn list_key(&self, kind: &str, question_filter: &QuestionFilter) -> Option<String> {
+        let generation = match self.generations.get("current").await {
+            Ok(generation) => generation,
+            Err(CoreError::NotFound) => {
+                // Another reader may start the first generation at the same time
+                let generation = Uuid::now_v7().to_string();
+                self.generations
+                    .set_if_absent("current", &generation, None)
+                    .await
+                    .ok()?;
+                self.generations.get("current").await.ok()?
+            }
+            Err(_) => return None,
+        };
+        let filter = serde_json::to_string(question_filter).ok()?;
+        Some(format!(
+            "{}:{}:{:x}",
+            generation,
+            kind,
+            Sha256::digest(filter.as_bytes())
+        ))
+    }
+
+    async fn cached_page<'a, F>(
+        &self,
+        kind: &str,
+        question_filter: &'a QuestionFilter,
+        load: F,
+    ) -> Result<PageEntity<QuestionEntity>, CoreError>
+    where
+        F: std::future::Future<Output = Result<PageEntity<QuestionEntity>, CoreError>> + 'a,
+    {
+        let Some(key) = self.list_key(kind, question_filter).await else {
+            return load.await;
+        };
+        if let Ok(page) = self.pages.get(&key).await {
+            return Ok(page);
+        }
+        let page = load.await?;
+        if let Err(err) = self.pages.set(&key, &page, Some(self.list_ttl)).await {
+            warn!("Failed to cache a page of questions: {}", err);
+        }
+        Ok(page)
+    }
+}
+
+#[async_trait]
+impl<C: CachePort + Clone + Send + Sync> QuestionPort for CachedQuestionRepository<C> {
+    async fn add(&self, question: CreateQuestionEntity) -> Result<QuestionEntity, CoreError> {
+        let question = self.question_port.add(question).await?;
+        self.remember(&question.id, Some(&question)).await;
+        self.invalidate_lists().await;
+        Ok(question)
+    }
+
+    async fn update(&self, question: QuestionEntity) -> Result<QuestionEntity, CoreError> {
+        let question_id = question.id.clone();
+        let question = self
+            .write(&question_id, self.question_port.update(question))
+            .await?;
+        self.remember(&question.id, Some(&question)).await;
+        self.invalidate_lists().await;
+        Ok(question)
+    }
+
+    async fn delete(
+        &self,
+        question_id: &QuestionId,
+        version: Option<i32>,
+    ) -> Result<(), CoreError> {
+        self.write(question_id, self.question_port.delete(question_id, version))
+            .await?;
+        self.remember(question_id, None).await;
+        self.invalidate_lists().await;
+        Ok(())
+    }
+
+    async fn restore(&self, question_id: &QuestionId) -> Result<QuestionEntity, CoreError> {
+        let question = self
+            .write(question_id, self.question_port.restore(question_id))
+            .await?;
+        self.remember(question_id, Some(&question)).await;
+        self.invalidate_lists().await;
+        Ok(question)
+    }
+
+    async fn purge(&self, question_id: &QuestionId) -> Result<(), CoreError> {
+        self.write(question_id, self.question_port.purge(question_id))
+            .await?;
+        self.remember(question_id, None).await;
+        self.invalidate_lists().await;
+        Ok(())
+    }
+
+    async fn get(&self, question_id: &QuestionId) -> Result<QuestionEntity, CoreError> {
+        if let Ok(question) = self.questions.get(&question_id.to_string()).await {
+            return question.ok_or(CoreError::NotFound);
+        }
+        match self.question_port.get(question_id).await {
+            Ok(question) => {
+                self.fill(question_id, Some(&question)).await;
+                Ok(question)
+            }
+            Err(CoreError::NotFound) => {
+                self.fill(question_id, None).await;
+                Err(CoreError::NotFound)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    async fn list(
+        &self,
+        question_filter: &QuestionFilter,
+    ) -> Result<PageEntity<QuestionEntity>, CoreError> {
+        self.cached_page(
+            "list",
+            question_filter,
+            self.question_port.list(question_filter),
+        )
+        .await
+    }
+
+    async fn search(
+        &self,
+        question_filter: &QuestionFilter,
+    ) -> Result<PageEntity<QuestionEntity>, CoreError> {
+        self.cached_page(
+            "search",
+            question_filter,
+            self.question_port.search(question_filter),
+        )
+        .await
+    }
+
+    async fn count(&self, question_filter: &QuestionFilter) -> Result<usize, CoreError> {
+        let Some(key) = self.list_key("count", question_filter).await else {
+            return self.question_port.count(question_filter).await;
+        };
+        if let Ok(total) = self.counts.get(&key).await {
+            return Ok(total);
+        }
+        let total = self.question_port.count(question_filter).await?;
+        if let Err(err) = self.counts.set(&key, &total, Some(self.list_ttl)).await {
+            warn!("Failed to cache a count of questions: {}", err);
+        }
+        Ok(total)
+    }
+}
@@ -3,6 +3,7 @@ use tonic::{
     transport::{Channel, Endpoint},
 };
 
+use common::errors::from_grpc_status;
 use common::grpc::gpt_answer::gpt_answer::{
     gpt_answer_service_client::GptAnswerServiceClient, GetAnswerPayload,
 };
@@ -39,11 +40,11 @@ impl GptAnswerClient {
     /// # Returns
     ///
     /// Returns a `Result` containing the connected `GptAnswerServiceClient` if successful,
-    /// or a `CoreError` if an error occurs during connection.
+    /// or `CoreError::Unavailable` if the service cannot be reached.
     pub async fn connect(&self) -> Result<GptAnswerServiceClient<Channel>, CoreError> {
         GptAnswerServiceClient::connect(self.endpoint.clone())
             .await
-            .map_err(|err| CoreError::InternalError(err.into()))
+            .map_err(|err| CoreError::Unavailable(err.to_string()))
     }
 }
 
@@ -58,7 +59,7 @@ impl GptAnswerPort for GptAnswerClient {
     /// # Returns
     ///
     /// Returns a `Result` containing the generated answer as a `String` if successful,
-    /// or a `CoreError` if an error occurs during communication with the service.
+    /// or the `CoreError` matching the gRPC status the service failed with.
     async fn get_answer(&self, question: &str) -> Result<String, CoreError> {
         let request = tonic::Request::new(GetAnswerPayload {
             question: question.to_string(),
@@ -69,7 +70,7 @@ impl GptAnswerPort for GptAnswerClient {
             .await?
             .get_answer(request)
             .await
-            .map_err(|err| CoreError::InternalError(err.into()))?;
+            .map_err(|status| from_grpc_status(&status))?;
 
         Ok(response.into_inner().answer)
     }
@@ -0,0 +1,66 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::sync::RwLock;
+
+use rust_core::common::errors::CoreError;
+use rust_core::entities::answer::{AnswerEntity, AnswerId, CreateAnswerEntity};
+use rust_core::entities::question::QuestionId;
+use rust_core::ports::answer::AnswerPort;
+
+use crate::repositories::in_memory::question::{QuestionInMemoryRepository, QuestionRecord};
+
+/// Stores answers next to the questions of a `QuestionInMemoryRepository`, so purging a question
+/// drops its answers like the `ON DELETE CASCADE` of the `answers` table.
+#[derive(Clone, Debug)]
+pub struct AnswerInMemoryRepository {
+    pub questions: Arc<RwLock<HashMap<QuestionId, QuestionRecord>>>,
+}
+
+impl AnswerInMemoryRepository {
+    pub fn new(question_repository: &QuestionInMemoryRepository) -> Self {
+        AnswerInMemoryRepository {
+            questions: question_repository.questions.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl AnswerPort for AnswerInMemoryRepository {
+    async fn add(
+        &self,
+        question_id: &QuestionId,
+        answer: CreateAnswerEntity,
+    ) -> Result<AnswerEntity, CoreError> {
+        let mut questions = self.questions.write().await;
+        let record = questions
+            .get_mut(question_id)
+            .filter(|record| !record.is_deleted())
+            .ok_or(CoreError::NotFound)?;
+        let answer = answer.into_entity(AnswerId::generate(), question_id.clone());
+        record.answers.push(answer.clone());
+        Ok(answer)
+    }
+
+    async fn get(&self, answer_id: &AnswerId) -> Result<AnswerEntity, CoreError> {
+        self.questions
+            .read()
+            .await
+            .values()
+            .flat_map(|record| &record.answers)
+            .find(|answer| &answer.id == answer_id)
+            .cloned()
+            .ok_or(CoreError::NotFound)
+    }
+
+    async fn list(&self, question_id: &QuestionId) -> Result<Vec<AnswerEntity>, CoreError> {
+        Ok(self
+            .questions
+            .read()
+            .await
+            .get(question_id)
+            .map(|record| record.answers.clone())
+            .unwrap_or_default())
+    }
+}
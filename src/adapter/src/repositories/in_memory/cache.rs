@@ -1,46 +1,338 @@
 use std::{
-    collections::HashMap,
-    sync::Arc,
-    time::{Duration, SystemTime},
+    collections::{BTreeMap, BTreeSet, HashMap},
+    sync::{Arc, Weak},
+    time::Duration,
 };
 
 use async_trait::async_trait;
-use tokio::sync::RwLock;
+use serde::Deserialize;
+use tokio::runtime::Handle;
+use tokio::sync::Mutex;
+use tokio::time::Instant;
 
 use rust_core::common::errors::CoreError;
 use rust_core::ports::cache::CachePort;
 
+/// How an `InMemoryCache` at capacity picks the entry to evict.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EvictionPolicy {
+    /// Evicts the least recently used entry.
+    #[default]
+    Lru,
+    /// Evicts the least frequently used entry, the least recently used one among ties.
+    Lfu,
+}
+
+/// Represents in-memory cache configuration.
+#[derive(Debug, Clone, Deserialize)]
+pub struct InMemoryCacheConfig {
+    /// Most entries held at once, unbounded when absent. Nothing is cached with zero.
+    pub max_entries: Option<usize>,
+    /// Most bytes of keys and values held at once, unbounded when absent.
+    pub max_bytes: Option<usize>,
+    /// Policy picking the entry to evict when the cache is full.
+    #[serde(default)]
+    pub eviction_policy: EvictionPolicy,
+    /// Seconds between background sweeps for expired entries, no sweeping when absent.
+    #[serde(default = "default_sweep_interval_secs")]
+    pub sweep_interval_secs: Option<u64>,
+}
+
+fn default_sweep_interval_secs() -> Option<u64> {
+    Some(60)
+}
+
+impl Default for InMemoryCacheConfig {
+    /// Unbounded, sweeping expired entries every minute.
+    fn default() -> Self {
+        Self {
+            max_entries: None,
+            max_bytes: None,
+            eviction_policy: EvictionPolicy::default(),
+            sweep_interval_secs: default_sweep_interval_secs(),
+        }
+    }
+}
+
+/// Counters and size of an `InMemoryCache`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Lookups that found a live entry.
+    pub hits: u64,
+    /// Lookups that found no entry or an expired one.
+    pub misses: u64,
+    /// Entries dropped to make room for others.
+    pub evictions: u64,
+    /// Entries dropped because they expired.
+    pub expirations: u64,
+    /// Entries currently held.
+    pub entries: usize,
+    /// Bytes of keys and values currently held.
+    pub bytes: usize,
+}
+
+/// Position of an entry in the eviction order, lowest first: its use count under
+/// `EvictionPolicy::Lfu` (zero under `EvictionPolicy::Lru`), then the tick it was last used at.
+type Rank = (u64, u64);
+
+/// A cached value together with its optional expiry time and eviction bookkeeping.
+struct CacheEntry {
+    value: String,
+    expiry_time: Option<Instant>,
+    uses: u64,
+    rank: Rank,
+}
+
+impl CacheEntry {
+    fn size(&self, key: &str) -> usize {
+        key.len() + self.value.len()
+    }
+}
+
+/// Entries indexed by key, by eviction order and by expiry time, so that every operation
+/// only touches the entries it affects.
+#[derive(Default)]
+struct CacheState {
+    entries: HashMap<String, CacheEntry>,
+    ranks: BTreeMap<Rank, String>,
+    expiries: BTreeSet<(Instant, String)>,
+    bytes: usize,
+    tick: u64,
+    stats: CacheStats,
+}
+
+impl CacheState {
+    fn next_rank(&mut self, policy: EvictionPolicy, uses: u64) -> Rank {
+        self.tick += 1;
+        match policy {
+            EvictionPolicy::Lru => (0, self.tick),
+            EvictionPolicy::Lfu => (uses, self.tick),
+        }
+    }
+
+    fn insert(&mut self, key: String, entry: CacheEntry) {
+        self.bytes += entry.size(&key);
+        self.ranks.insert(entry.rank, key.clone());
+        if let Some(expiry_time) = entry.expiry_time {
+            self.expiries.insert((expiry_time, key.clone()));
+        }
+        self.entries.insert(key, entry);
+    }
+
+    fn remove(&mut self, key: &str) -> Option<CacheEntry> {
+        let entry = self.entries.remove(key)?;
+        self.bytes -= entry.size(key);
+        self.ranks.remove(&entry.rank);
+        if let Some(expiry_time) = entry.expiry_time {
+            self.expiries.remove(&(expiry_time, key.to_string()));
+        }
+        Some(entry)
+    }
+
+    /// Looks up an entry, dropping it when it expired by `now`.
+    fn live(&mut self, key: &str, now: Instant) -> Option<&CacheEntry> {
+        if self
+            .entries
+            .get(key)?
+            .expiry_time
+            .is_some_and(|expiry_time| expiry_time <= now)
+        {
+            self.remove(key);
+            self.stats.expirations += 1;
+            return None;
+        }
+        self.entries.get(key)
+    }
+
+    /// Looks up a value the way `get` does, counting a hit or a miss.
+    fn lookup(&mut self, key: &str, policy: EvictionPolicy) -> Option<String> {
+        let Some(value) = self
+            .live(key, Instant::now())
+            .map(|entry| entry.value.clone())
+        else {
+            self.stats.misses += 1;
+            return None;
+        };
+        self.touch(key, policy);
+        self.stats.hits += 1;
+        Some(value)
+    }
+
+    /// Replaces the value of a key, making room for it first. The key is only dropped when no
+    /// room can be made.
+    fn put(
+        &mut self,
+        config: &InMemoryCacheConfig,
+        key: &str,
+        value: String,
+        expiry_time: Option<Instant>,
+    ) {
+        self.remove(key);
+        if !self.make_room(config, key.len() + value.len()) {
+            return;
+        }
+        let rank = self.next_rank(config.eviction_policy, 1);
+        self.insert(
+            key.to_string(),
+            CacheEntry {
+                value,
+                expiry_time,
+                uses: 1,
+                rank,
+            },
+        );
+    }
+
+    /// Replaces the value of an entry, keeping its expiry time and its place in the eviction
+    /// order, then records a use of it. The entry is only dropped when no room can be made.
+    fn replace(&mut self, config: &InMemoryCacheConfig, key: &str, value: String) {
+        let Some(entry) = self.remove(key) else {
+            return;
+        };
+        if !self.make_room(config, key.len() + value.len()) {
+            return;
+        }
+        self.insert(key.to_string(), CacheEntry { value, ..entry });
+        self.touch(key, config.eviction_policy);
+    }
+
+    /// Records a use of the entry, moving it to the back of the eviction order.
+    fn touch(&mut self, key: &str, policy: EvictionPolicy) {
+        let Some(uses) = self.entries.get(key).map(|entry| entry.uses + 1) else {
+            return;
+        };
+        let rank = self.next_rank(policy, uses);
+        if let Some(entry) = self.entries.get_mut(key) {
+            let previous = std::mem::replace(&mut entry.rank, rank);
+            entry.uses = uses;
+            self.ranks.remove(&previous);
+            self.ranks.insert(rank, key.to_string());
+        }
+    }
+
+    /// Drops the entries that expired by `now`.
+    fn sweep(&mut self, now: Instant) {
+        while let Some((expiry_time, key)) = self.expiries.first().cloned() {
+            if expiry_time > now {
+                break;
+            }
+            self.remove(&key);
+            self.stats.expirations += 1;
+        }
+    }
+
+    /// Evicts entries until `size` more bytes fit next to one more entry, returning whether they
+    /// do.
+    fn make_room(&mut self, config: &InMemoryCacheConfig, size: usize) -> bool {
+        let is_full = |state: &CacheState| {
+            config
+                .max_entries
+                .is_some_and(|max_entries| state.entries.len() >= max_entries)
+                || config
+                    .max_bytes
+                    .is_some_and(|max_bytes| state.bytes + size > max_bytes)
+        };
+        if !is_full(self) {
+            return true;
+        }
+
+        self.sweep(Instant::now());
+        while is_full(self) {
+            let Some((_, key)) = self.ranks.first_key_value() else {
+                return false;
+            };
+            let key = key.clone();
+            self.remove(&key);
+            self.stats.evictions += 1;
+        }
+        true
+    }
+}
+
 /// Represents an in-memory cache implementation.
+///
+/// The cache holds at most `max_entries` entries and `max_bytes` bytes, evicting entries by its
+/// `EvictionPolicy` to make room. Expired entries are dropped when looked up, when room is
+/// needed and by a background sweep. Clones share the same entries.
+#[derive(Clone)]
 pub struct InMemoryCache {
-    cache: Arc<RwLock<HashMap<String, (String, Option<SystemTime>)>>>,
+    config: InMemoryCacheConfig,
+    // A mutex rather than a read-write lock: every lookup moves the entry in the eviction order
+    // and counts a hit or a miss, so reads need exclusive access as much as writes do.
+    cache: Arc<Mutex<CacheState>>,
 }
 
 impl Default for InMemoryCache {
     /// Creates a new default instance of in-memory cache.
     fn default() -> Self {
-        Self {
-            cache: Arc::new(RwLock::default()),
-        }
+        Self::new(InMemoryCacheConfig::default())
     }
 }
 
 impl InMemoryCache {
-    /// Removes expired entries from the cache asynchronously.
+    /// Creates an in-memory cache with the given capacity and policy.
     ///
-    /// This method is used to remove any expired cache entries based on their expiration time.
-    ///
-    /// # Returns
-    ///
-    /// Returns `Ok(())` if the cleanup is successful, otherwise returns a `CoreError`.
-    async fn cleanup_expired_entries(&self) -> Result<(), CoreError> {
-        let mut cache = self.cache.write().await;
-        let now = SystemTime::now();
-        cache.retain(|_, (_, expiry_time)| match expiry_time {
-            Some(exp) => *exp > now,
-            None => true, // No expiration time, don't remove
-        });
+    /// The background sweep only runs when the cache is created inside a Tokio runtime, and
+    /// stops once the cache and its clones are dropped.
+    pub fn new(config: InMemoryCacheConfig) -> Self {
+        let cache = Arc::new(Mutex::new(CacheState::default()));
+        if let (Some(interval), Ok(handle)) = (config.sweep_interval_secs, Handle::try_current()) {
+            handle.spawn(sweep_periodically(
+                Arc::downgrade(&cache),
+                Instant::now(),
+                Duration::from_secs(interval),
+            ));
+        }
+        Self { config, cache }
+    }
+
+    /// Drops every entry, keeping the counters.
+    pub async fn clear(&self) {
+        let mut cache = self.cache.lock().await;
+        cache.entries.clear();
+        cache.ranks.clear();
+        cache.expiries.clear();
+        cache.bytes = 0;
+    }
+
+    /// Fails with `CoreError::InvalidParameter` when a key and value alone exceed `max_bytes`.
+    fn check_size(&self, key: &str, value: &str) -> Result<(), CoreError> {
+        let size = key.len() + value.len();
+        if self
+            .config
+            .max_bytes
+            .is_some_and(|max_bytes| size > max_bytes)
+        {
+            return Err(CoreError::InvalidParameter(format!(
+                "{} bytes exceed the cache capacity",
+                size
+            )));
+        }
         Ok(())
     }
+
+    /// Returns the counters and current size of the cache.
+    pub async fn stats(&self) -> CacheStats {
+        let cache = self.cache.lock().await;
+        CacheStats {
+            entries: cache.entries.len(),
+            bytes: cache.bytes,
+            ..cache.stats
+        }
+    }
+}
+
+/// Drops expired entries every `interval` from `start` on, until the cache is dropped.
+async fn sweep_periodically(cache: Weak<Mutex<CacheState>>, start: Instant, interval: Duration) {
+    let mut ticker = tokio::time::interval_at(start + interval, interval);
+    loop {
+        ticker.tick().await;
+        let Some(cache) = cache.upgrade() else {
+            break;
+        };
+        cache.lock().await.sweep(Instant::now());
+    }
 }
 
 #[async_trait]
@@ -56,15 +348,11 @@ impl CachePort for InMemoryCache {
     /// Returns a `Result` containing `value` if the key is found and not expired,
     /// or throw `CoreError::NotFound` if the key is not found or expired.
     async fn get(&self, key: &str) -> Result<String, CoreError> {
-        let cache = self.cache.read().await;
-        match cache.get(key) {
-            Some((value, expiry_time))
-                if expiry_time.map_or(true, |exp| exp > SystemTime::now()) =>
-            {
-                Ok(value.clone())
-            }
-            _ => Err(CoreError::NotFound),
-        }
+        self.cache
+            .lock()
+            .await
+            .lookup(key, self.config.eviction_policy)
+            .ok_or(CoreError::NotFound)
     }
 
     /// Sets a key-value pair in the cache with an optional expiration duration.
@@ -77,22 +365,22 @@ impl CachePort for InMemoryCache {
     ///
     /// # Returns
     ///
-    /// Returns `Ok(())` if the set operation is successful, otherwise returns a `CoreError`.
+    /// Returns `Ok(())` if the set operation is successful, or `CoreError::InvalidParameter` if
+    /// the key and value alone exceed `max_bytes`.
     async fn set(
         &self,
         key: &str,
         value: &str,
         expiration: Option<Duration>,
     ) -> Result<(), CoreError> {
-        self.cleanup_expired_entries().await?;
+        self.check_size(key, value)?;
 
         // Calculate expiry_time based on expiration duration
-        let expiry_time = expiration.map(|exp| SystemTime::now() + exp);
-
+        let expiry_time = expiration.map(|exp| Instant::now() + exp);
         self.cache
-            .write()
+            .lock()
             .await
-            .insert(key.to_string(), (value.to_string(), expiry_time));
+            .put(&self.config, key, value.to_string(), expiry_time);
         Ok(())
     }
 
@@ -105,12 +393,117 @@ impl CachePort for InMemoryCache {
     /// # Returns
     ///
     /// Returns `Ok(())` if the key is found and removed, otherwise returns a `CoreError`.
-    async fn del(&mut self, key: &str) -> Result<(), CoreError> {
+    async fn del(&self, key: &str) -> Result<(), CoreError> {
         self.cache
-            .write()
+            .lock()
             .await
             .remove(key)
             .map(|_| ())
             .ok_or(CoreError::NotFound)
     }
+
+    /// Retrieves the values of several keys at once, counting a hit or a miss for each.
+    async fn mget(&self, keys: &[&str]) -> Result<Vec<Option<String>>, CoreError> {
+        let mut cache = self.cache.lock().await;
+        Ok(keys
+            .iter()
+            .map(|key| cache.lookup(key, self.config.eviction_policy))
+            .collect())
+    }
+
+    /// Sets several key-value pairs under one lock, or fails with `CoreError::InvalidParameter`
+    /// without setting any when one of them alone exceeds `max_bytes`.
+    async fn mset(
+        &self,
+        entries: &[(&str, &str)],
+        expiration: Option<Duration>,
+    ) -> Result<(), CoreError> {
+        for (key, value) in entries {
+            self.check_size(key, value)?;
+        }
+
+        let expiry_time = expiration.map(|exp| Instant::now() + exp);
+        let mut cache = self.cache.lock().await;
+        for (key, value) in entries {
+            cache.put(&self.config, key, value.to_string(), expiry_time);
+        }
+        Ok(())
+    }
+
+    async fn exists(&self, key: &str) -> Result<bool, CoreError> {
+        Ok(self.cache.lock().await.live(key, Instant::now()).is_some())
+    }
+
+    async fn ttl(&self, key: &str) -> Result<Option<Duration>, CoreError> {
+        let now = Instant::now();
+        let mut cache = self.cache.lock().await;
+        let entry = cache.live(key, now).ok_or(CoreError::NotFound)?;
+        Ok(entry
+            .expiry_time
+            .map(|expiry_time| expiry_time.saturating_duration_since(now)))
+    }
+
+    async fn expire(&self, key: &str, expiration: Duration) -> Result<(), CoreError> {
+        let now = Instant::now();
+        let mut cache = self.cache.lock().await;
+        cache.live(key, now).ok_or(CoreError::NotFound)?;
+        if let Some(mut entry) = cache.remove(key) {
+            entry.expiry_time = Some(now + expiration);
+            cache.insert(key.to_string(), entry);
+        }
+        Ok(())
+    }
+
+    /// Adds to the integer stored under `key`, keeping its expiration, or fails with
+    /// `CoreError::InvalidParameter` when the value is not an integer or the sum overflows.
+    async fn incr_by(&self, key: &str, delta: i64) -> Result<i64, CoreError> {
+        let mut cache = self.cache.lock().await;
+        let current = match cache.live(key, Instant::now()) {
+            Some(entry) => Some(entry.value.parse::<i64>().map_err(|_| {
+                CoreError::InvalidParameter(format!("value of {} is not an integer", key))
+            })?),
+            None => None,
+        };
+        let value = current.unwrap_or(0).checked_add(delta).ok_or_else(|| {
+            CoreError::InvalidParameter(format!("incrementing {} would overflow", key))
+        })?;
+        match current {
+            Some(_) => cache.replace(&self.config, key, value.to_string()),
+            None => cache.put(&self.config, key, value.to_string(), None),
+        }
+        Ok(value)
+    }
+
+    async fn set_if_absent(
+        &self,
+        key: &str,
+        value: &str,
+        expiration: Option<Duration>,
+    ) -> Result<bool, CoreError> {
+        self.check_size(key, value)?;
+
+        let now = Instant::now();
+        let mut cache = self.cache.lock().await;
+        if cache.live(key, now).is_some() {
+            return Ok(false);
+        }
+        let expiry_time = expiration.map(|exp| now + exp);
+        cache.put(&self.config, key, value.to_string(), expiry_time);
+        Ok(true)
+    }
+
+    async fn delete_by_pattern(&self, prefix: &str) -> Result<usize, CoreError> {
+        let mut cache = self.cache.lock().await;
+        cache.sweep(Instant::now());
+        let keys: Vec<String> = cache
+            .entries
+            .keys()
+            .filter(|key| key.starts_with(prefix))
+            .cloned()
+            .collect();
+        for key in &keys {
+            cache.remove(key);
+        }
+        Ok(keys.len())
+    }
 }
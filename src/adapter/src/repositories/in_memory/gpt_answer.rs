@@ -0,0 +1,33 @@
+use async_trait::async_trait;
+
+use rust_core::common::errors::CoreError;
+use rust_core::ports::gpt_answer::GptAnswerPort;
+
+/// Answer given by a `GptAnswerInMemoryRepository` created with `Default`.
+pub const DEFAULT_ANSWER: &str = "This answer was generated without a GPT answer service.";
+
+/// Answers every question with the same configured text, standing in for the GPT answer gRPC
+/// service in local runs and tests.
+#[derive(Clone, Debug)]
+pub struct GptAnswerInMemoryRepository {
+    pub answer: String,
+}
+
+impl GptAnswerInMemoryRepository {
+    pub fn new(answer: String) -> Self {
+        GptAnswerInMemoryRepository { answer }
+    }
+}
+
+impl Default for GptAnswerInMemoryRepository {
+    fn default() -> Self {
+        GptAnswerInMemoryRepository::new(DEFAULT_ANSWER.to_string())
+    }
+}
+
+#[async_trait]
+impl GptAnswerPort for GptAnswerInMemoryRepository {
+    async fn get_answer(&self, _question: &str) -> Result<String, CoreError> {
+        Ok(self.answer.clone())
+    }
+}
@@ -1,17 +1,202 @@
+use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::str::FromStr;
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use chrono::Utc;
 use tokio::sync::RwLock;
 
 use rust_core::common::errors::CoreError;
-use rust_core::entities::question::{QuestionEntity, QuestionId};
+use rust_core::entities::answer::AnswerEntity;
+use rust_core::entities::cursor_entity::{CursorEntity, CursorValue};
+use rust_core::entities::page_entity::PageEntity;
+use rust_core::entities::question::{CreateQuestionEntity, QuestionEntity, QuestionId};
 use rust_core::entities::question_filter::QuestionFilter;
+use rust_core::entities::question_revision::QuestionRevisionEntity;
+use rust_core::entities::sort_entity::{SortDirection, SortEntity};
+use rust_core::entities::tag::normalize_tags;
 use rust_core::ports::question::QuestionPort;
 
+/// A stored question along with the bookkeeping the Postgres tables keep next to it.
+#[derive(Clone, Debug)]
+pub struct QuestionRecord {
+    pub question: QuestionEntity,
+    /// Revisions of the question, oldest first, like the `question_revisions` table.
+    pub revisions: Vec<QuestionRevisionEntity>,
+    /// Answers to the question, oldest first, like the `answers` table.
+    pub answers: Vec<AnswerEntity>,
+}
+
+impl QuestionRecord {
+    /// Whether the question has been soft deleted.
+    pub fn is_deleted(&self) -> bool {
+        self.question.deleted_on.is_some()
+    }
+
+    /// Applies a change to the question, moving it to the next version like an `UPDATE` would.
+    fn touch(&mut self, question: QuestionEntity) -> &QuestionEntity {
+        self.question = QuestionEntity {
+            version: self.question.version + 1,
+            created_on: self.question.created_on,
+            updated_on: Utc::now(),
+            ..question
+        };
+        &self.question
+    }
+
+    /// Checks whether the record satisfies every criterion of the filter.
+    ///
+    /// Mirrors the `WHERE` clause built by the Postgres adapter so both return the same rows.
+    fn matches(&self, question_filter: &QuestionFilter) -> bool {
+        let tags_match = question_filter.tags.as_ref().is_none_or(|wanted| {
+            let tags = self.question.tags.as_deref().unwrap_or_default();
+            wanted.iter().all(|tag| tags.contains(tag))
+        });
+        let title_match = question_filter.title.as_ref().is_none_or(|title| {
+            self.question
+                .title
+                .to_lowercase()
+                .contains(&title.to_lowercase())
+        });
+        let after_match = question_filter
+            .created_after
+            .is_none_or(|after| self.question.created_on > after);
+        let before_match = question_filter
+            .created_before
+            .is_none_or(|before| self.question.created_on < before);
+        let deleted_match = question_filter.include_deleted || !self.is_deleted();
+        let search_match = question_filter
+            .search
+            .as_ref()
+            .is_none_or(|terms| self.relevance(terms).is_some());
+
+        tags_match && title_match && after_match && before_match && deleted_match && search_match
+    }
+
+    /// Scores the record against every word of `terms`, or `None` when one of them is missing.
+    ///
+    /// A simple stand-in for the `search_vector` column: words are compared without stemming,
+    /// and title hits count more than content hits like the weights `ts_rank` applies.
+    fn relevance(&self, terms: &str) -> Option<f32> {
+        let terms = tokenize(terms);
+        if terms.is_empty() {
+            return None;
+        }
+        let title = tokenize(&self.question.title);
+        let content = tokenize(&self.question.content);
+        terms.iter().try_fold(0.0, |score, term| {
+            let title_hits = title.iter().filter(|word| *word == term).count();
+            let content_hits = content.iter().filter(|word| *word == term).count();
+            match title_hits + content_hits {
+                0 => None,
+                _ => Some(
+                    score + TITLE_WEIGHT * title_hits as f32 + CONTENT_WEIGHT * content_hits as f32,
+                ),
+            }
+        })
+    }
+
+    /// Value of the sort key `field` on this record, as stored in a cursor.
+    fn cursor_value(&self, field: &str) -> Result<CursorValue, CoreError> {
+        match field {
+            "id" => Ok(CursorValue::Text(self.question.id.to_string())),
+            "title" => Ok(CursorValue::Text(self.question.title.clone())),
+            "created_on" => Ok(CursorValue::Timestamp(self.question.created_on)),
+            field => Err(CoreError::InvalidParameter(format!(
+                "sort: `{field}` is not sortable"
+            ))),
+        }
+    }
+
+    /// Builds the cursor pointing at this record for the given sort order.
+    fn cursor(&self, sort: &[SortEntity]) -> Result<CursorEntity, CoreError> {
+        Ok(CursorEntity {
+            sort: sort.to_vec(),
+            values: sort
+                .iter()
+                .map(|key| self.cursor_value(&key.field))
+                .collect::<Result<Vec<_>, _>>()?,
+            id: self.question.id.to_string(),
+        })
+    }
+
+    /// Checks whether the record sorts strictly after the cursor position.
+    ///
+    /// Mirrors the keyset predicate built by the Postgres adapter.
+    fn is_after(&self, cursor: &CursorEntity) -> Result<bool, CoreError> {
+        for (key, value) in cursor.sort.iter().zip(&cursor.values) {
+            let ordering = match (key.field.as_str(), value) {
+                ("id", CursorValue::Text(id)) => self.question.id.cmp(&QuestionId::from_str(id)?),
+                ("title", CursorValue::Text(title)) => self.question.title.as_str().cmp(title),
+                ("created_on", CursorValue::Timestamp(created_on)) => {
+                    self.question.created_on.cmp(created_on)
+                }
+                (field, _) => {
+                    return Err(CoreError::InvalidParameter(format!(
+                        "cursor: unexpected value for `{field}`"
+                    )))
+                }
+            };
+            let ordering = match key.direction {
+                SortDirection::Asc => ordering,
+                SortDirection::Desc => ordering.reverse(),
+            };
+            if ordering.is_ne() {
+                return Ok(ordering.is_gt());
+            }
+        }
+        Ok(self.question.id > QuestionId::from_str(&cursor.id)?)
+    }
+}
+
+/// Weight of a search hit in the title, matching the `A` weight of `ts_rank`.
+const TITLE_WEIGHT: f32 = 1.0;
+/// Weight of a search hit in the content, matching the `B` weight of `ts_rank`.
+const CONTENT_WEIGHT: f32 = 0.4;
+
+/// Splits text into lowercase words.
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Compares two records on a single field.
+type RecordComparator = fn(&QuestionRecord, &QuestionRecord) -> Ordering;
+
+/// Resolves a sort key into the comparison the Postgres adapter's `ORDER BY` performs.
+fn comparator(key: &SortEntity) -> Result<RecordComparator, CoreError> {
+    match key.field.as_str() {
+        "id" => Ok(|a, b| a.question.id.cmp(&b.question.id)),
+        "title" => Ok(|a, b| a.question.title.cmp(&b.question.title)),
+        "created_on" => Ok(|a, b| a.question.created_on.cmp(&b.question.created_on)),
+        field => Err(CoreError::InvalidParameter(format!(
+            "sort: `{field}` is not sortable"
+        ))),
+    }
+}
+
+/// Conflict reported when a write was based on an older version than `current`.
+fn stale_version(current: &QuestionEntity) -> CoreError {
+    CoreError::Conflict(format!(
+        "question {} is at version {}",
+        current.id, current.version
+    ))
+}
+
+/// Conflict reported when purging a question that was never soft deleted.
+fn not_deleted(current: &QuestionEntity) -> CoreError {
+    CoreError::Conflict(format!(
+        "question {} must be deleted before it is purged",
+        current.id
+    ))
+}
+
 #[derive(Clone, Debug)]
 pub struct QuestionInMemoryRepository {
-    pub questions: Arc<RwLock<HashMap<QuestionId, QuestionEntity>>>,
+    pub questions: Arc<RwLock<HashMap<QuestionId, QuestionRecord>>>,
 }
 
 impl Default for QuestionInMemoryRepository {
@@ -30,26 +215,82 @@ impl QuestionInMemoryRepository {
 
 #[async_trait]
 impl QuestionPort for QuestionInMemoryRepository {
-    async fn add(&self, question: QuestionEntity) -> Result<QuestionEntity, CoreError> {
-        self.questions
-            .write()
-            .await
-            .insert(question.id.clone(), question.clone());
-        Ok(question.clone())
+    async fn add(&self, question: CreateQuestionEntity) -> Result<QuestionEntity, CoreError> {
+        let question = CreateQuestionEntity {
+            tags: normalize_tags(question.tags),
+            ..question
+        }
+        .into_entity(QuestionId::generate());
+        self.questions.write().await.insert(
+            question.id.clone(),
+            QuestionRecord {
+                question: question.clone(),
+                revisions: vec![QuestionRevisionEntity::of(&question)],
+                answers: Vec::new(),
+            },
+        );
+        Ok(question)
     }
 
     async fn update(&self, question: QuestionEntity) -> Result<QuestionEntity, CoreError> {
-        self.get(&question.id).await?;
-        self.questions
-            .write()
-            .await
-            .insert(question.id.clone(), question.clone());
-        Ok(question.clone())
+        let mut questions = self.questions.write().await;
+        let record = questions
+            .get_mut(&question.id)
+            .filter(|record| !record.is_deleted())
+            .ok_or(CoreError::NotFound)?;
+        if record.question.version != question.version {
+            return Err(stale_version(&record.question));
+        }
+        let question = record
+            .touch(QuestionEntity {
+                tags: normalize_tags(question.tags),
+                deleted_on: None,
+                ..question
+            })
+            .clone();
+        record.revisions.push(QuestionRevisionEntity::of(&question));
+        Ok(question)
     }
 
-    async fn delete(&self, question_id: &QuestionId) -> Result<(), CoreError> {
-        self.get(question_id).await?;
-        self.questions.write().await.remove(question_id);
+    async fn delete(
+        &self,
+        question_id: &QuestionId,
+        version: Option<i32>,
+    ) -> Result<(), CoreError> {
+        let mut questions = self.questions.write().await;
+        let record = questions
+            .get_mut(question_id)
+            .filter(|record| !record.is_deleted())
+            .ok_or(CoreError::NotFound)?;
+        if version.is_some_and(|version| version != record.question.version) {
+            return Err(stale_version(&record.question));
+        }
+        record.touch(QuestionEntity {
+            deleted_on: Some(Utc::now()),
+            ..record.question.clone()
+        });
+        Ok(())
+    }
+
+    async fn restore(&self, question_id: &QuestionId) -> Result<QuestionEntity, CoreError> {
+        let mut questions = self.questions.write().await;
+        let record = questions.get_mut(question_id).ok_or(CoreError::NotFound)?;
+        if record.is_deleted() {
+            record.touch(QuestionEntity {
+                deleted_on: None,
+                ..record.question.clone()
+            });
+        }
+        Ok(record.question.clone())
+    }
+
+    async fn purge(&self, question_id: &QuestionId) -> Result<(), CoreError> {
+        let mut questions = self.questions.write().await;
+        let record = questions.get(question_id).ok_or(CoreError::NotFound)?;
+        if !record.is_deleted() {
+            return Err(not_deleted(&record.question));
+        }
+        questions.remove(question_id);
         Ok(())
     }
 
@@ -59,22 +300,109 @@ impl QuestionPort for QuestionInMemoryRepository {
             .read()
             .await
             .get(question_id)
+            .filter(|record| !record.is_deleted())
             .ok_or(CoreError::NotFound)?
+            .question
             .clone())
     }
 
     async fn list(
         &self,
         question_filter: &QuestionFilter,
-    ) -> Result<Vec<QuestionEntity>, CoreError> {
+    ) -> Result<PageEntity<QuestionEntity>, CoreError> {
+        let pagination = &question_filter.pagination;
+        let sort = pagination.sort.as_deref().unwrap_or_default();
+        let comparators = sort
+            .iter()
+            .map(|key| comparator(key).map(|compare| (compare, key.direction)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let questions = self.questions.read().await;
+        let mut records = Vec::new();
+        for record in questions.values() {
+            let after_cursor = match &pagination.cursor {
+                Some(cursor) => record.is_after(cursor)?,
+                None => true,
+            };
+            if after_cursor && record.matches(question_filter) {
+                records.push(record);
+            }
+        }
+        records.sort_by(|a, b| {
+            comparators
+                .iter()
+                .map(|(compare, direction)| match direction {
+                    SortDirection::Asc => compare(a, b),
+                    SortDirection::Desc => compare(b, a),
+                })
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or_else(|| a.question.id.cmp(&b.question.id))
+        });
+
+        // Fetch one extra record to find out whether another page follows.
+        let mut records = records
+            .into_iter()
+            .skip(pagination.offset())
+            .take(pagination.limit() + 1)
+            .collect::<Vec<_>>();
+        let next_cursor = if records.len() > pagination.limit() {
+            records.truncate(pagination.limit());
+            records
+                .last()
+                .map(|record| record.cursor(sort)?.encode())
+                .transpose()?
+        } else {
+            None
+        };
+
+        Ok(PageEntity {
+            items: records
+                .into_iter()
+                .map(|record| record.question.clone())
+                .collect::<Vec<_>>(),
+            next_cursor,
+        })
+    }
+
+    async fn search(
+        &self,
+        question_filter: &QuestionFilter,
+    ) -> Result<PageEntity<QuestionEntity>, CoreError> {
+        let Some(terms) = &question_filter.search else {
+            return self.list(question_filter).await;
+        };
+        let pagination = &question_filter.pagination;
+
+        let questions = self.questions.read().await;
+        let mut ranked = questions
+            .values()
+            .filter(|record| record.matches(question_filter))
+            .filter_map(|record| Some((record.relevance(terms)?, record)))
+            .collect::<Vec<_>>();
+        ranked.sort_by(|(a_rank, a), (b_rank, b)| {
+            b_rank
+                .total_cmp(a_rank)
+                .then_with(|| a.question.id.cmp(&b.question.id))
+        });
+
+        Ok(PageEntity {
+            items: ranked
+                .into_iter()
+                .skip(pagination.offset())
+                .take(pagination.limit())
+                .map(|(_, record)| record.question.clone())
+                .collect::<Vec<_>>(),
+            next_cursor: None,
+        })
+    }
+
+    async fn count(&self, question_filter: &QuestionFilter) -> Result<usize, CoreError> {
         Ok(self
             .questions
             .read()
             .await
             .values()
-            .skip(question_filter.pagination.start)
-            .take(question_filter.pagination.end - question_filter.pagination.start)
-            .cloned()
-            .collect::<Vec<_>>())
+            .filter(|record| record.matches(question_filter))
+            .count())
     }
 }
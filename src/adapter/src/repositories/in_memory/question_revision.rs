@@ -0,0 +1,61 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::sync::RwLock;
+
+use rust_core::common::errors::CoreError;
+use rust_core::entities::question::QuestionId;
+use rust_core::entities::question_revision::QuestionRevisionEntity;
+use rust_core::ports::question_revision::QuestionRevisionPort;
+
+use crate::repositories::in_memory::question::{QuestionInMemoryRepository, QuestionRecord};
+
+/// Reads the revisions `QuestionInMemoryRepository` records next to each question.
+#[derive(Clone, Debug)]
+pub struct QuestionRevisionInMemoryRepository {
+    pub questions: Arc<RwLock<HashMap<QuestionId, QuestionRecord>>>,
+}
+
+impl QuestionRevisionInMemoryRepository {
+    pub fn new(question_repository: &QuestionInMemoryRepository) -> Self {
+        QuestionRevisionInMemoryRepository {
+            questions: question_repository.questions.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl QuestionRevisionPort for QuestionRevisionInMemoryRepository {
+    async fn list(
+        &self,
+        question_id: &QuestionId,
+    ) -> Result<Vec<QuestionRevisionEntity>, CoreError> {
+        Ok(self
+            .questions
+            .read()
+            .await
+            .get(question_id)
+            .map(|record| record.revisions.clone())
+            .unwrap_or_default())
+    }
+
+    async fn get(
+        &self,
+        question_id: &QuestionId,
+        revision: i32,
+    ) -> Result<QuestionRevisionEntity, CoreError> {
+        self.questions
+            .read()
+            .await
+            .get(question_id)
+            .and_then(|record| {
+                record
+                    .revisions
+                    .iter()
+                    .find(|entry| entry.revision == revision)
+            })
+            .cloned()
+            .ok_or(CoreError::NotFound)
+    }
+}
@@ -0,0 +1,48 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::sync::RwLock;
+
+use rust_core::common::errors::CoreError;
+use rust_core::entities::question::QuestionId;
+use rust_core::entities::tag::TagEntity;
+use rust_core::ports::tag::TagPort;
+
+use crate::repositories::in_memory::question::{QuestionInMemoryRepository, QuestionRecord};
+
+/// Counts the tags of the questions held by a `QuestionInMemoryRepository`.
+#[derive(Clone, Debug)]
+pub struct TagInMemoryRepository {
+    pub questions: Arc<RwLock<HashMap<QuestionId, QuestionRecord>>>,
+}
+
+impl TagInMemoryRepository {
+    pub fn new(question_repository: &QuestionInMemoryRepository) -> Self {
+        TagInMemoryRepository {
+            questions: question_repository.questions.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl TagPort for TagInMemoryRepository {
+    async fn list(&self) -> Result<Vec<TagEntity>, CoreError> {
+        let mut counts = HashMap::<String, usize>::new();
+        for record in self.questions.read().await.values() {
+            if record.is_deleted() {
+                continue;
+            }
+            for tag in record.question.tags.iter().flatten() {
+                *counts.entry(tag.clone()).or_default() += 1;
+            }
+        }
+
+        let mut tags = counts
+            .into_iter()
+            .map(|(name, count)| TagEntity { name, count })
+            .collect::<Vec<_>>();
+        tags.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name)));
+        Ok(tags)
+    }
+}
@@ -1,3 +1,4 @@
+pub mod cached;
 pub mod grpc;
 pub mod in_memory;
 pub mod postgres;
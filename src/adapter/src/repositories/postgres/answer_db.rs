@@ -0,0 +1,113 @@
+use async_trait::async_trait;
+use deadpool_diesel::postgres::Pool;
+use diesel::dsl::exists;
+use diesel::{select, ExpressionMethods, QueryDsl, RunQueryDsl, SelectableHelper};
+
+use rust_core::common::errors::CoreError;
+use rust_core::entities::answer::{AnswerEntity, AnswerId, CreateAnswerEntity};
+use rust_core::entities::question::QuestionId;
+use rust_core::ports::answer::AnswerPort;
+
+use crate::repositories::postgres::errors::{interact_error, pool_error, query_error};
+use crate::repositories::postgres::models::answer::{AnswerModel, NewAnswerModel};
+use crate::repositories::postgres::schema::answers::dsl::{
+    answers, created_at, id as answer_id_column, question_id as answer_question_id,
+};
+use crate::repositories::postgres::schema::questions::dsl::{deleted_at, questions};
+
+#[derive(Clone)]
+pub struct AnswerDBRepository {
+    pub db: Pool,
+}
+
+impl AnswerDBRepository {
+    pub fn new(db: Pool) -> Self {
+        AnswerDBRepository { db }
+    }
+}
+
+#[async_trait]
+impl AnswerPort for AnswerDBRepository {
+    async fn add(
+        &self,
+        question_id: &QuestionId,
+        answer: CreateAnswerEntity,
+    ) -> Result<AnswerEntity, CoreError> {
+        let answer =
+            NewAnswerModel::from(answer.into_entity(AnswerId::generate(), question_id.clone()));
+        self.db
+            .get()
+            .await
+            .map_err(pool_error)?
+            .interact(move |conn| {
+                let question_is_live = select(exists(
+                    questions
+                        .find(answer.question_id)
+                        .filter(deleted_at.is_null()),
+                ))
+                .get_result::<bool>(conn)
+                .map_err(query_error)?;
+                if !question_is_live {
+                    return Err(CoreError::NotFound);
+                }
+
+                let response = diesel::insert_into(answers)
+                    .values(&answer)
+                    .returning(AnswerModel::as_returning())
+                    .get_result(conn)
+                    .map_err(|err| match err {
+                        // The question was purged between the check and the insert.
+                        diesel::result::Error::DatabaseError(
+                            diesel::result::DatabaseErrorKind::ForeignKeyViolation,
+                            _,
+                        ) => CoreError::NotFound,
+                        _ => query_error(err),
+                    })?
+                    .into();
+
+                Ok(response)
+            })
+            .await
+            .map_err(interact_error)?
+    }
+
+    async fn get(&self, answer_id: &AnswerId) -> Result<AnswerEntity, CoreError> {
+        let answer_id = answer_id.0;
+        self.db
+            .get()
+            .await
+            .map_err(pool_error)?
+            .interact(move |conn| {
+                let response = answers
+                    .select(AnswerModel::as_select())
+                    .find(answer_id)
+                    .first(conn)
+                    .map_err(query_error)?
+                    .into();
+
+                Ok(response)
+            })
+            .await
+            .map_err(interact_error)?
+    }
+
+    async fn list(&self, question_id: &QuestionId) -> Result<Vec<AnswerEntity>, CoreError> {
+        let question_id = question_id.0;
+        self.db
+            .get()
+            .await
+            .map_err(pool_error)?
+            .interact(move |conn| {
+                let answer_list = answers
+                    .select(AnswerModel::as_select())
+                    .filter(answer_question_id.eq(question_id))
+                    .order((created_at.asc(), answer_id_column.asc()))
+                    .load::<AnswerModel>(conn)
+                    .map_err(query_error)?;
+
+                Ok(answer_list.into_iter().map(|a| a.into()).collect())
+            })
+            .await
+            .map_err(interact_error)?
+    }
+}
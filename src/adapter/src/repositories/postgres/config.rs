@@ -4,4 +4,7 @@ use serde::Deserialize;
 pub struct DBConfig {
     pub url: String,
     pub max_size: usize,
+    /// Applies pending migrations before serving.
+    #[serde(default)]
+    pub run_migrations: bool,
 }
@@ -0,0 +1,63 @@
+use anyhow::anyhow;
+use deadpool_diesel::postgres::PoolError;
+use deadpool_diesel::InteractError;
+use diesel::result::{DatabaseErrorKind, Error};
+use tracing::warn;
+
+use rust_core::common::errors::CoreError;
+use rust_core::common::validation::ValidationErrors;
+
+/// Field a check violation is reported under when the database does not name the column.
+const UNKNOWN_FIELD: &str = "request";
+
+/// Maps a failure to check a connection out of the pool.
+///
+/// The pool only fails when the database cannot be reached in time or the pool was closed, so
+/// the caller may try again later.
+pub fn pool_error(err: PoolError) -> CoreError {
+    CoreError::Unavailable(err.to_string())
+}
+
+/// Maps a failure to run a closure on a pooled connection.
+pub fn interact_error(err: InteractError) -> CoreError {
+    match err {
+        InteractError::Panic(_) => CoreError::InternalError(anyhow!("database task panicked")),
+        InteractError::Aborted => CoreError::Unavailable("database task aborted".to_string()),
+    }
+}
+
+/// Maps a failed Diesel query.
+///
+/// Unique violations are reported as `CoreError::Conflict`, check violations as
+/// `CoreError::Validation` and a lost connection as `CoreError::Unavailable`. Messages from the
+/// database name tables and constraints, so they are logged rather than passed on to the client.
+pub fn query_error(err: Error) -> CoreError {
+    match err {
+        Error::NotFound => CoreError::NotFound,
+        Error::DatabaseError(DatabaseErrorKind::UniqueViolation, info) => {
+            warn!(
+                constraint = info.constraint_name(),
+                "Unique violation: {}",
+                info.message()
+            );
+            CoreError::Conflict("already exists".to_string())
+        }
+        Error::DatabaseError(DatabaseErrorKind::CheckViolation, info) => {
+            warn!(
+                constraint = info.constraint_name(),
+                "Check violation: {}",
+                info.message()
+            );
+            let mut errors = ValidationErrors::default();
+            errors.add(
+                info.column_name().unwrap_or(UNKNOWN_FIELD),
+                "is not allowed",
+            );
+            CoreError::Validation(errors)
+        }
+        Error::DatabaseError(DatabaseErrorKind::ClosedConnection, info) => {
+            CoreError::Unavailable(info.message().to_string())
+        }
+        _ => CoreError::InternalError(err.into()),
+    }
+}
@@ -0,0 +1,7 @@
+-- This file should undo anything in `up.sql`
+-- UUIDs do not fit in an integer, so every question is renumbered from a fresh sequence.
+CREATE SEQUENCE questions_id_seq;
+ALTER TABLE questions ALTER COLUMN id TYPE INTEGER USING nextval('questions_id_seq');
+ALTER TABLE questions ALTER COLUMN id SET DEFAULT nextval('questions_id_seq');
+ALTER SEQUENCE questions_id_seq OWNED BY questions.id;
+SELECT setval('questions_id_seq', COALESCE(MAX(id), 0) + 1, false) FROM questions;
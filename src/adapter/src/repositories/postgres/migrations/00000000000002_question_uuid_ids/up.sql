@@ -0,0 +1,5 @@
+-- Question ids become opaque UUIDs allocated by the application. Existing serial ids are kept
+-- recognisable by left padding their hex value, e.g. 26 becomes 00000000-0000-0000-0000-00000000001a.
+ALTER TABLE questions ALTER COLUMN id DROP DEFAULT;
+ALTER TABLE questions ALTER COLUMN id TYPE UUID USING lpad(to_hex(id), 32, '0')::uuid;
+DROP SEQUENCE IF EXISTS questions_id_seq;
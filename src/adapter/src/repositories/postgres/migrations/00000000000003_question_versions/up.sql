@@ -0,0 +1 @@
+ALTER TABLE questions ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP TRIGGER IF EXISTS set_updated_at ON questions;
+ALTER TABLE questions DROP COLUMN updated_at;
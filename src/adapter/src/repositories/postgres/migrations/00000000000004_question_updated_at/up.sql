@@ -0,0 +1,4 @@
+-- `diesel_manage_updated_at` keeps `updated_at` current on every update
+ALTER TABLE questions ADD COLUMN updated_at TIMESTAMP NOT NULL DEFAULT NOW();
+UPDATE questions SET updated_at = created_on;
+SELECT diesel_manage_updated_at('questions');
@@ -0,0 +1,2 @@
+-- Deleted questions are kept until purged, with `deleted_at` recording when they were deleted
+ALTER TABLE questions ADD COLUMN deleted_at TIMESTAMP;
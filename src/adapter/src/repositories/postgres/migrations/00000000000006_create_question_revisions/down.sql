@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP TRIGGER IF EXISTS record_question_revision ON questions;
+DROP FUNCTION IF EXISTS record_question_revision();
+DROP TABLE question_revisions;
@@ -0,0 +1,31 @@
+CREATE TABLE IF NOT EXISTS question_revisions
+(
+    question_id UUID         NOT NULL REFERENCES questions (id) ON DELETE CASCADE,
+    revision    INTEGER      NOT NULL,
+    title       VARCHAR(255) NOT NULL,
+    content     TEXT         NOT NULL,
+    tags        TEXT[],
+    created_on  TIMESTAMP    NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (question_id, revision)
+);
+
+-- Existing questions start their history at their current version
+INSERT INTO question_revisions (question_id, revision, title, content, tags, created_on)
+SELECT id, version, title, content, tags, updated_at
+FROM questions;
+
+-- Snapshots a question whenever its editable fields are written. The revision is the question
+-- version, so soft deletes and restores, which only bump the version, leave no revision behind.
+CREATE OR REPLACE FUNCTION record_question_revision() RETURNS trigger AS $$
+BEGIN
+    INSERT INTO question_revisions (question_id, revision, title, content, tags, created_on)
+    VALUES (NEW.id, NEW.version, NEW.title, NEW.content, NEW.tags, NEW.updated_at);
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER record_question_revision
+    AFTER INSERT OR UPDATE OF title, content, tags
+    ON questions
+    FOR EACH ROW
+EXECUTE PROCEDURE record_question_revision();
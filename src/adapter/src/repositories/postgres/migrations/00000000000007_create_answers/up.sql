@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS answers
+(
+    id           UUID PRIMARY KEY,
+    question_id  UUID         NOT NULL REFERENCES questions (id) ON DELETE CASCADE,
+    body         TEXT         NOT NULL,
+    source_model VARCHAR(255),
+    created_at   TIMESTAMP    NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS answers_question_id_idx ON answers (question_id);
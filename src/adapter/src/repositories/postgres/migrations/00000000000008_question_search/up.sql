@@ -0,0 +1,8 @@
+-- Titles weigh more than content when ranking matches with `ts_rank`
+ALTER TABLE questions
+    ADD COLUMN search_vector TSVECTOR NOT NULL GENERATED ALWAYS AS (
+        setweight(to_tsvector('english', title), 'A') ||
+        setweight(to_tsvector('english', content), 'B')
+    ) STORED;
+
+CREATE INDEX questions_search_vector_idx ON questions USING GIN (search_vector);
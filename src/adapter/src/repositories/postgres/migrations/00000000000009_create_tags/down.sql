@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+DROP TRIGGER sync_question_tags ON questions;
+DROP FUNCTION sync_question_tags();
+ALTER TABLE questions DROP CONSTRAINT questions_tags_not_null;
+DROP TABLE question_tags;
+DROP TABLE tags;
@@ -0,0 +1,62 @@
+CREATE TABLE IF NOT EXISTS tags
+(
+    id   SERIAL PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE
+);
+
+CREATE TABLE IF NOT EXISTS question_tags
+(
+    question_id UUID    NOT NULL REFERENCES questions (id) ON DELETE CASCADE,
+    tag_id      INTEGER NOT NULL REFERENCES tags (id) ON DELETE CASCADE,
+    PRIMARY KEY (question_id, tag_id)
+);
+
+CREATE INDEX IF NOT EXISTS question_tags_tag_id_idx ON question_tags (tag_id);
+
+-- Bring existing tags in line with what the application writes: trimmed, lowercased, without
+-- blanks or duplicates. This is a cleanup, so it neither bumps `updated_at` nor records revisions.
+ALTER TABLE questions DISABLE TRIGGER USER;
+UPDATE questions
+SET tags = ARRAY(
+    SELECT name
+    FROM (SELECT lower(trim(tag)) AS name, min(position) AS position
+          FROM unnest(questions.tags) WITH ORDINALITY AS question_tag(tag, position)
+          WHERE trim(tag) <> ''
+          GROUP BY 1) AS normalized
+    ORDER BY position)
+WHERE tags IS NOT NULL;
+ALTER TABLE questions ENABLE TRIGGER USER;
+
+ALTER TABLE questions
+    ADD CONSTRAINT questions_tags_not_null CHECK (array_position(tags, NULL) IS NULL);
+
+INSERT INTO tags (name)
+SELECT DISTINCT unnest(tags)
+FROM questions;
+
+INSERT INTO question_tags (question_id, tag_id)
+SELECT questions.id, tags.id
+FROM questions
+         JOIN tags ON tags.name = ANY (questions.tags);
+
+-- Keeps `tags` and `question_tags` in step with the tags a question carries
+CREATE OR REPLACE FUNCTION sync_question_tags() RETURNS trigger AS $$
+BEGIN
+    INSERT INTO tags (name)
+    SELECT DISTINCT unnest(NEW.tags)
+    ON CONFLICT (name) DO NOTHING;
+
+    DELETE FROM question_tags WHERE question_id = NEW.id;
+    INSERT INTO question_tags (question_id, tag_id)
+    SELECT NEW.id, tags.id
+    FROM tags
+    WHERE tags.name = ANY (NEW.tags);
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER sync_question_tags
+    AFTER INSERT OR UPDATE OF tags
+    ON questions
+    FOR EACH ROW
+EXECUTE PROCEDURE sync_question_tags();
@@ -0,0 +1,136 @@
+use anyhow::anyhow;
+use deadpool_diesel::postgres::Pool;
+use diesel::migration::MigrationSource;
+use diesel::pg::Pg;
+use diesel::sql_types::BigInt;
+use diesel::{sql_query, PgConnection, RunQueryDsl};
+use diesel_migrations::MigrationHarness;
+
+use rust_core::common::errors::CoreError;
+
+use crate::repositories::postgres::errors::{interact_error, pool_error, query_error};
+use crate::repositories::postgres::question_db::MIGRATIONS;
+
+/// Key of the advisory lock held while migrating, "question" in ASCII.
+const MIGRATION_LOCK_KEY: i64 = 0x7175_6573_7469_6f6e;
+
+/// Whether one of the embedded migrations has been applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationStatus {
+    pub version: String,
+    pub applied: bool,
+}
+
+/// Applies and reverts the migrations embedded in `MIGRATIONS`.
+///
+/// Every change happens under a Postgres advisory lock, so replicas starting at the same time
+/// migrate one after the other instead of racing.
+#[derive(Clone)]
+pub struct Migrator {
+    pub db: Pool,
+}
+
+impl Migrator {
+    pub fn new(db: Pool) -> Self {
+        Migrator { db }
+    }
+
+    /// Applies the pending migrations, returning their versions in the order they ran.
+    pub async fn up(&self) -> Result<Vec<String>, CoreError> {
+        self.interact(|conn| {
+            locked(conn, |conn| {
+                let versions = conn
+                    .run_pending_migrations(MIGRATIONS)
+                    .map_err(|err| CoreError::InternalError(anyhow!(err)))?;
+                Ok(versions.iter().map(ToString::to_string).collect())
+            })
+        })
+        .await
+    }
+
+    /// Reverts the last applied migration, returning its version, or fails with
+    /// `CoreError::NotFound` when none is applied.
+    pub async fn down(&self) -> Result<String, CoreError> {
+        self.interact(|conn| locked(conn, revert_last)).await
+    }
+
+    /// Reverts the last applied migration and applies it again, returning its version, or fails
+    /// with `CoreError::NotFound` when none is applied.
+    pub async fn redo(&self) -> Result<String, CoreError> {
+        self.interact(|conn| {
+            locked(conn, |conn| {
+                let version = revert_last(conn)?;
+                conn.run_next_migration(MIGRATIONS)
+                    .map_err(|err| CoreError::InternalError(anyhow!(err)))?;
+                Ok(version)
+            })
+        })
+        .await
+    }
+
+    /// Lists every embedded migration, oldest first, with whether it has been applied.
+    pub async fn status(&self) -> Result<Vec<MigrationStatus>, CoreError> {
+        self.interact(|conn| {
+            let applied = conn
+                .applied_migrations()
+                .map_err(|err| CoreError::InternalError(anyhow!(err)))?;
+            let migrations = MigrationSource::<Pg>::migrations(&MIGRATIONS)
+                .map_err(|err| CoreError::InternalError(anyhow!(err)))?;
+            Ok(migrations
+                .iter()
+                .map(|migration| {
+                    let version = migration.name().version();
+                    MigrationStatus {
+                        version: version.to_string(),
+                        applied: applied.contains(&version),
+                    }
+                })
+                .collect())
+        })
+        .await
+    }
+
+    async fn interact<T, F>(&self, f: F) -> Result<T, CoreError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut PgConnection) -> Result<T, CoreError> + Send + 'static,
+    {
+        self.db
+            .get()
+            .await
+            .map_err(pool_error)?
+            .interact(f)
+            .await
+            .map_err(interact_error)?
+    }
+}
+
+/// Runs `f` while holding the migration advisory lock, waiting for other holders to finish.
+fn locked<T>(
+    conn: &mut PgConnection,
+    f: impl FnOnce(&mut PgConnection) -> Result<T, CoreError>,
+) -> Result<T, CoreError> {
+    sql_query("SELECT pg_advisory_lock($1)")
+        .bind::<BigInt, _>(MIGRATION_LOCK_KEY)
+        .execute(conn)
+        .map_err(query_error)?;
+    let result = f(conn);
+    sql_query("SELECT pg_advisory_unlock($1)")
+        .bind::<BigInt, _>(MIGRATION_LOCK_KEY)
+        .execute(conn)
+        .map_err(query_error)?;
+    result
+}
+
+fn revert_last(conn: &mut PgConnection) -> Result<String, CoreError> {
+    if conn
+        .applied_migrations()
+        .map_err(|err| CoreError::InternalError(anyhow!(err)))?
+        .is_empty()
+    {
+        return Err(CoreError::NotFound);
+    }
+    conn.revert_last_migration(MIGRATIONS)
+        .map(|version| version.to_string())
+        .map_err(|err| CoreError::InternalError(anyhow!(err)))
+}
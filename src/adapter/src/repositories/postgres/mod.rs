@@ -1,4 +1,9 @@
+pub mod answer_db;
 pub mod config;
+pub mod errors;
+pub mod migrator;
 pub mod models;
 pub mod question_db;
+pub mod question_revision_db;
 pub mod schema;
+pub mod tag_db;
@@ -0,0 +1,51 @@
+use std::time::SystemTime;
+
+use chrono::{DateTime, Utc};
+use diesel::{Insertable, Queryable, Selectable};
+use serde::Serialize;
+use uuid::Uuid;
+
+use rust_core::entities::answer::{AnswerEntity, AnswerId};
+use rust_core::entities::question::QuestionId;
+
+#[derive(Debug, Queryable, Serialize, Selectable)]
+#[diesel(table_name = super::super::schema::answers)]
+pub struct AnswerModel {
+    pub id: Uuid,
+    pub question_id: Uuid,
+    pub body: String,
+    pub source_model: Option<String>,
+    pub created_at: SystemTime,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = super::super::schema::answers)]
+pub struct NewAnswerModel {
+    pub id: Uuid,
+    pub question_id: Uuid,
+    pub body: String,
+    pub source_model: Option<String>,
+}
+
+impl From<AnswerEntity> for NewAnswerModel {
+    fn from(entity: AnswerEntity) -> Self {
+        NewAnswerModel {
+            id: entity.id.0,
+            question_id: entity.question_id.0,
+            body: entity.body,
+            source_model: entity.source_model,
+        }
+    }
+}
+
+impl From<AnswerModel> for AnswerEntity {
+    fn from(model: AnswerModel) -> Self {
+        AnswerEntity {
+            id: AnswerId(model.id),
+            question_id: QuestionId(model.question_id),
+            body: model.body,
+            source_model: model.source_model,
+            created_at: DateTime::<Utc>::from(model.created_at),
+        }
+    }
+}
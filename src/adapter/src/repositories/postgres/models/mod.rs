@@ -1 +1,3 @@
+pub mod answer;
 pub mod question;
+pub mod question_revision;
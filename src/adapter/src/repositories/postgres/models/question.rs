@@ -1,15 +1,16 @@
-use std::io::{Error, ErrorKind};
 use std::time::SystemTime;
 
+use chrono::{DateTime, Utc};
 use diesel::{AsChangeset, Identifiable, Insertable, Queryable, Selectable};
 use serde::Serialize;
+use uuid::Uuid;
 
 use rust_core::entities::question::{QuestionEntity, QuestionId};
 
-#[derive(Debug, Queryable, Serialize, Selectable, Insertable, AsChangeset, Identifiable)]
+#[derive(Debug, Queryable, Serialize, Selectable, Identifiable)]
 #[diesel(table_name = super::super::schema::questions)]
 pub struct QuestionModel {
-    pub id: i32,
+    pub id: Uuid,
     /// Title of the question.
     pub title: String,
     /// Content of the question.
@@ -18,35 +19,76 @@ pub struct QuestionModel {
     pub tags: Option<Vec<Option<String>>>,
 
     pub created_on: SystemTime,
+    /// Revision of the question, bumped on every update.
+    pub version: i32,
+    /// Set by the `diesel_manage_updated_at` trigger whenever the row changes.
+    pub updated_at: SystemTime,
+    /// Set while the question is soft deleted.
+    pub deleted_at: Option<SystemTime>,
 }
 
-impl TryFrom<QuestionEntity> for QuestionModel {
-    type Error = Error;
+/// Row inserted for a new question; `created_on` and `version` are filled in by the database.
+#[derive(Debug, Insertable)]
+#[diesel(table_name = super::super::schema::questions)]
+pub struct NewQuestionModel {
+    pub id: Uuid,
+    /// Title of the question.
+    pub title: String,
+    /// Content of the question.
+    pub content: String,
+    /// Optional tags associated with the question.
+    pub tags: Option<Vec<Option<String>>>,
+}
+
+impl From<QuestionEntity> for NewQuestionModel {
+    fn from(entity: QuestionEntity) -> Self {
+        NewQuestionModel {
+            id: entity.id.0,
+            title: entity.title,
+            content: entity.content,
+            tags: entity.tags.map(|v| v.into_iter().map(Some).collect()),
+        }
+    }
+}
 
-    fn try_from(entity: QuestionEntity) -> Result<QuestionModel, Self::Error> {
-        let id = entity
-            .id
-            .0
-            .parse()
-            .map_err(|_| Error::new(ErrorKind::InvalidInput, "Invalid ID"))?;
+/// Columns written when a question is updated.
+///
+/// `created_on` is left alone, and `updated_at` is left to the `diesel_manage_updated_at` trigger.
+#[derive(Debug, AsChangeset)]
+#[diesel(table_name = super::super::schema::questions)]
+pub struct QuestionChangesetModel {
+    /// Title of the question.
+    pub title: String,
+    /// Content of the question.
+    pub content: String,
+    /// Optional tags associated with the question.
+    pub tags: Option<Vec<Option<String>>>,
+    /// Revision the question moves to.
+    pub version: i32,
+}
 
-        Ok(QuestionModel {
-            id,
+impl From<QuestionEntity> for QuestionChangesetModel {
+    fn from(entity: QuestionEntity) -> Self {
+        QuestionChangesetModel {
             title: entity.title,
             content: entity.content,
             tags: entity.tags.map(|v| v.into_iter().map(Some).collect()),
-            created_on: SystemTime::now(),
-        })
+            version: entity.version,
+        }
     }
 }
 
-impl Into<QuestionEntity> for QuestionModel {
-    fn into(self) -> QuestionEntity {
+impl From<QuestionModel> for QuestionEntity {
+    fn from(model: QuestionModel) -> Self {
         QuestionEntity {
-            id: QuestionId(self.id.to_string()),
-            title: self.title,
-            content: self.content,
-            tags: self.tags.map(|v| v.into_iter().flatten().collect()),
+            id: QuestionId(model.id),
+            title: model.title,
+            content: model.content,
+            tags: model.tags.map(|v| v.into_iter().flatten().collect()),
+            version: model.version,
+            created_on: DateTime::<Utc>::from(model.created_on),
+            updated_on: DateTime::<Utc>::from(model.updated_at),
+            deleted_on: model.deleted_at.map(DateTime::<Utc>::from),
         }
     }
 }
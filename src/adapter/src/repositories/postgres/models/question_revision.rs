@@ -0,0 +1,38 @@
+use std::time::SystemTime;
+
+use chrono::{DateTime, Utc};
+use diesel::{Queryable, Selectable};
+use serde::Serialize;
+use uuid::Uuid;
+
+use rust_core::entities::question::QuestionId;
+use rust_core::entities::question_revision::QuestionRevisionEntity;
+
+/// Row of `question_revisions`, written by the `record_question_revision` trigger.
+#[derive(Debug, Queryable, Serialize, Selectable)]
+#[diesel(table_name = super::super::schema::question_revisions)]
+pub struct QuestionRevisionModel {
+    pub question_id: Uuid,
+    pub revision: i32,
+    /// Title of the question at that revision.
+    pub title: String,
+    /// Content of the question at that revision.
+    pub content: String,
+    /// Tags of the question at that revision.
+    pub tags: Option<Vec<Option<String>>>,
+
+    pub created_on: SystemTime,
+}
+
+impl From<QuestionRevisionModel> for QuestionRevisionEntity {
+    fn from(model: QuestionRevisionModel) -> Self {
+        QuestionRevisionEntity {
+            question_id: QuestionId(model.question_id),
+            revision: model.revision,
+            title: model.title,
+            content: model.content,
+            tags: model.tags.map(|v| v.into_iter().flatten().collect()),
+            created_on: DateTime::<Utc>::from(model.created_on),
+        }
+    }
+}
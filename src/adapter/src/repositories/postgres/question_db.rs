@@ -1,18 +1,43 @@
+use std::str::FromStr;
+use std::time::SystemTime;
+
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use deadpool_diesel::postgres::Pool;
+use diesel::dsl::{exists, now, sql};
+use diesel::expression::SqlLiteral;
+use diesel::pg::Pg;
+use diesel::sql_types::{Bool, Text};
+use diesel::PgConnection;
 use diesel::{
-    delete, insert_into, update, ExpressionMethods, QueryDsl, RunQueryDsl, SelectableHelper,
+    delete, insert_into, select, update, BoolExpressionMethods, BoxableExpression,
+    ExpressionMethods, NullableExpressionMethods, OptionalExtension, PgArrayExpressionMethods,
+    PgTextExpressionMethods, QueryDsl, RunQueryDsl, SelectableHelper,
+};
+use diesel_full_text_search::configuration::TsConfigurationByName;
+use diesel_full_text_search::{
+    ts_rank, websearch_to_tsquery_with_search_config, TsVectorExtensions,
 };
 use diesel_migrations::{embed_migrations, EmbeddedMigrations};
+use uuid::Uuid;
 
 use rust_core::common::errors::CoreError;
-use rust_core::entities::question::{QuestionEntity, QuestionId};
+use rust_core::entities::cursor_entity::{CursorEntity, CursorValue};
+use rust_core::entities::page_entity::PageEntity;
+use rust_core::entities::question::{CreateQuestionEntity, QuestionEntity, QuestionId};
 use rust_core::entities::question_filter::QuestionFilter;
+use rust_core::entities::sort_entity::{SortDirection, SortEntity};
+use rust_core::entities::tag::normalize_tags;
 use rust_core::ports::question::QuestionPort;
 
-use crate::repositories::postgres::models::question::QuestionModel;
+use crate::repositories::postgres::errors::{interact_error, pool_error, query_error};
+use crate::repositories::postgres::models::question::{
+    NewQuestionModel, QuestionChangesetModel, QuestionModel,
+};
 use crate::repositories::postgres::schema::questions::dsl::questions;
-use crate::repositories::postgres::schema::questions::id;
+use crate::repositories::postgres::schema::questions::{
+    created_on, deleted_at, id, search_vector, tags, title, version, BoxedQuery,
+};
 
 // NOTE: path relative to Cargo.toml
 pub const MIGRATIONS: EmbeddedMigrations =
@@ -29,119 +54,475 @@ impl QuestionDBRepository {
     }
 }
 
+/// Text search configuration `search_vector` is generated with.
+const SEARCH_CONFIGURATION: TsConfigurationByName = TsConfigurationByName("english");
+
+/// The title column compared byte by byte, matching how Rust orders strings.
+fn title_bytewise() -> SqlLiteral<Text> {
+    sql::<Text>(r#"questions.title COLLATE "C""#)
+}
+
+/// A boxed `WHERE` condition on the questions table.
+type QuestionPredicate = Box<dyn BoxableExpression<questions, Pg, SqlType = Bool>>;
+
+/// Matches rows whose `field` equals the value captured in a cursor.
+fn column_eq(field: &str, value: &CursorValue) -> Result<QuestionPredicate, CoreError> {
+    match (field, value) {
+        ("id", CursorValue::Text(value)) => Ok(Box::new(id.eq(QuestionId::from_str(value)?.0))),
+        ("title", CursorValue::Text(value)) => Ok(Box::new(title.eq(value.clone()))),
+        ("created_on", CursorValue::Timestamp(value)) => {
+            Ok(Box::new(created_on.eq(SystemTime::from(*value))))
+        }
+        (field, _) => Err(CoreError::InvalidParameter(format!(
+            "cursor: unexpected value for `{field}`"
+        ))),
+    }
+}
+
+/// Matches rows whose `field` sorts strictly after the value captured in a cursor.
+fn column_after(
+    field: &str,
+    value: &CursorValue,
+    direction: SortDirection,
+) -> Result<QuestionPredicate, CoreError> {
+    match (field, value, direction) {
+        ("id", CursorValue::Text(value), SortDirection::Asc) => {
+            Ok(Box::new(id.gt(QuestionId::from_str(value)?.0)))
+        }
+        ("id", CursorValue::Text(value), SortDirection::Desc) => {
+            Ok(Box::new(id.lt(QuestionId::from_str(value)?.0)))
+        }
+        ("title", CursorValue::Text(value), SortDirection::Asc) => {
+            Ok(Box::new(title_bytewise().gt(value.clone())))
+        }
+        ("title", CursorValue::Text(value), SortDirection::Desc) => {
+            Ok(Box::new(title_bytewise().lt(value.clone())))
+        }
+        ("created_on", CursorValue::Timestamp(value), SortDirection::Asc) => {
+            Ok(Box::new(created_on.gt(SystemTime::from(*value))))
+        }
+        ("created_on", CursorValue::Timestamp(value), SortDirection::Desc) => {
+            Ok(Box::new(created_on.lt(SystemTime::from(*value))))
+        }
+        (field, _, _) => Err(CoreError::InvalidParameter(format!(
+            "cursor: unexpected value for `{field}`"
+        ))),
+    }
+}
+
+/// Keyset predicate matching every row that sorts after the cursor.
+///
+/// Built from the innermost key outwards as `k1 > v1 OR (k1 = v1 AND (k2 > v2 OR ...))`, with the
+/// id as the final tie breaker.
+fn after_cursor(cursor: &CursorEntity) -> Result<QuestionPredicate, CoreError> {
+    let mut predicate = column_after(
+        "id",
+        &CursorValue::Text(cursor.id.clone()),
+        SortDirection::Asc,
+    )?;
+    for (key, value) in cursor.sort.iter().zip(&cursor.values).rev() {
+        predicate = Box::new(
+            column_after(&key.field, value, key.direction)?
+                .or(column_eq(&key.field, value)?.and(predicate)),
+        );
+    }
+    Ok(predicate)
+}
+
+/// Builds the cursor pointing at `question` for the given sort order.
+fn cursor_of(question: &QuestionModel, sort: &[SortEntity]) -> Result<CursorEntity, CoreError> {
+    let values = sort
+        .iter()
+        .map(|key| match key.field.as_str() {
+            "id" => Ok(CursorValue::Text(question.id.to_string())),
+            "title" => Ok(CursorValue::Text(question.title.clone())),
+            "created_on" => Ok(CursorValue::Timestamp(DateTime::<Utc>::from(
+                question.created_on,
+            ))),
+            field => Err(CoreError::InvalidParameter(format!(
+                "sort: `{field}` is not sortable"
+            ))),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(CursorEntity {
+        sort: sort.to_vec(),
+        values,
+        id: question.id.to_string(),
+    })
+}
+
+/// Selects every question matching the filter criteria, leaving out pagination.
+fn filtered_questions(question_filter: &QuestionFilter) -> BoxedQuery<'static, Pg> {
+    let mut query = questions.into_boxed();
+    if let Some(wanted) = &question_filter.tags {
+        query = query.filter(tags.contains(wanted.iter().cloned().map(Some).collect::<Vec<_>>()));
+    }
+    if let Some(value) = &question_filter.title {
+        query = query.filter(title.ilike(format!("%{}%", escape_like(value))));
+    }
+    if let Some(after) = question_filter.created_after {
+        query = query.filter(created_on.gt(SystemTime::from(after)));
+    }
+    if let Some(before) = question_filter.created_before {
+        query = query.filter(created_on.lt(SystemTime::from(before)));
+    }
+    if !question_filter.include_deleted {
+        query = query.filter(deleted_at.is_null());
+    }
+    if let Some(terms) = &question_filter.search {
+        query = query.filter(
+            search_vector.matches(websearch_to_tsquery_with_search_config(
+                SEARCH_CONFIGURATION,
+                terms.clone(),
+            )),
+        );
+    }
+    query
+}
+
+/// Explains why a write guarded by a version check touched no row.
+fn missing_or_stale(conn: &mut PgConnection, question_id: Uuid) -> CoreError {
+    let current = questions
+        .find(question_id)
+        .filter(deleted_at.is_null())
+        .select(version)
+        .first::<i32>(conn)
+        .optional();
+    match current {
+        Ok(Some(current)) => CoreError::Conflict(format!(
+            "question {} is at version {current}",
+            QuestionId(question_id)
+        )),
+        Ok(None) => CoreError::NotFound,
+        Err(err) => query_error(err),
+    }
+}
+
+/// Explains why purging a question touched no row.
+fn missing_or_live(conn: &mut PgConnection, question_id: Uuid) -> CoreError {
+    let exists = select(exists(questions.find(question_id))).get_result::<bool>(conn);
+    match exists {
+        Ok(true) => CoreError::Conflict(format!(
+            "question {} must be deleted before it is purged",
+            QuestionId(question_id)
+        )),
+        Ok(false) => CoreError::NotFound,
+        Err(err) => query_error(err),
+    }
+}
+
+/// Escapes the `LIKE` wildcards in `value` so it only matches literally.
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
 #[async_trait]
 impl QuestionPort for QuestionDBRepository {
-    async fn add(&self, question: QuestionEntity) -> Result<QuestionEntity, CoreError> {
+    async fn add(&self, question: CreateQuestionEntity) -> Result<QuestionEntity, CoreError> {
         self.db
             .get()
             .await
-            .unwrap()
+            .map_err(pool_error)?
             .interact(move |conn| {
-                let question = QuestionModel::try_from(question)
-                    .map_err(|err| CoreError::InternalError(err.into()))?;
+                let question = CreateQuestionEntity {
+                    tags: normalize_tags(question.tags),
+                    ..question
+                };
+                let question = NewQuestionModel::from(question.into_entity(QuestionId::generate()));
                 let response = insert_into(questions)
                     .values(&question)
+                    .returning(QuestionModel::as_returning())
                     .get_result::<QuestionModel>(conn)
-                    .map_err(|err| match err {
-                        diesel::result::Error::NotFound => CoreError::NotFound,
-                        _ => CoreError::InternalError(err.into()),
-                    })
-                    .unwrap();
+                    .map_err(query_error)?;
                 Ok(response.into())
             })
             .await
-            .unwrap()
+            .map_err(interact_error)?
     }
 
     async fn update(&self, question: QuestionEntity) -> Result<QuestionEntity, CoreError> {
         self.db
             .get()
             .await
-            .unwrap()
+            .map_err(pool_error)?
             .interact(move |conn| {
-                let question = QuestionModel::try_from(question)
-                    .map_err(|err| CoreError::InternalError(err.into()))?;
-                let response = update(questions.filter(id.eq(question.id)))
-                    .set(&question)
-                    .get_result::<QuestionModel>(conn)
-                    .map_err(|err| match err {
-                        diesel::result::Error::NotFound => CoreError::NotFound,
-                        _ => CoreError::InternalError(err.into()),
-                    })?
-                    .into();
+                let question_id = question.id.0;
+                let expected_version = question.version;
+                let changes = QuestionChangesetModel {
+                    version: expected_version + 1,
+                    ..QuestionChangesetModel::from(QuestionEntity {
+                        tags: normalize_tags(question.tags),
+                        ..question
+                    })
+                };
+                let response = update(
+                    questions
+                        .filter(id.eq(question_id))
+                        .filter(deleted_at.is_null())
+                        .filter(version.eq(expected_version)),
+                )
+                .set(&changes)
+                .returning(QuestionModel::as_returning())
+                .get_result::<QuestionModel>(conn)
+                .optional()
+                .map_err(query_error)?;
 
-                Ok(response)
+                match response {
+                    Some(response) => Ok(response.into()),
+                    None => Err(missing_or_stale(conn, question_id)),
+                }
+            })
+            .await
+            .map_err(interact_error)?
+    }
+
+    async fn delete(
+        &self,
+        question_id: &QuestionId,
+        expected_version: Option<i32>,
+    ) -> Result<(), CoreError> {
+        let question_id = question_id.0;
+        self.db
+            .get()
+            .await
+            .map_err(pool_error)?
+            .interact(move |conn| {
+                let mut query = update(questions)
+                    .set((deleted_at.eq(now.nullable()), version.eq(version + 1)))
+                    .filter(id.eq(question_id))
+                    .filter(deleted_at.is_null())
+                    .into_boxed();
+                if let Some(expected_version) = expected_version {
+                    query = query.filter(version.eq(expected_version));
+                }
+                let deleted = query.execute(conn).map_err(query_error)?;
+
+                match deleted {
+                    0 => Err(missing_or_stale(conn, question_id)),
+                    _ => Ok(()),
+                }
+            })
+            .await
+            .map_err(interact_error)?
+    }
+
+    async fn restore(&self, question_id: &QuestionId) -> Result<QuestionEntity, CoreError> {
+        let question_id = question_id.0;
+        self.db
+            .get()
+            .await
+            .map_err(pool_error)?
+            .interact(move |conn| {
+                let restored = update(
+                    questions
+                        .filter(id.eq(question_id))
+                        .filter(deleted_at.is_not_null()),
+                )
+                .set((deleted_at.eq(None::<SystemTime>), version.eq(version + 1)))
+                .returning(QuestionModel::as_returning())
+                .get_result::<QuestionModel>(conn)
+                .optional()
+                .map_err(query_error)?;
+                if let Some(restored) = restored {
+                    return Ok(restored.into());
+                }
+
+                // Not deleted, restoring leaves it as it is
+                questions
+                    .select(QuestionModel::as_select())
+                    .find(question_id)
+                    .first(conn)
+                    .optional()
+                    .map_err(query_error)?
+                    .map(QuestionEntity::from)
+                    .ok_or(CoreError::NotFound)
             })
             .await
-            .unwrap()
+            .map_err(interact_error)?
     }
 
-    async fn delete(&self, question_id: &QuestionId) -> Result<(), CoreError> {
-        let question_id: i32 = question_id.to_string().parse()?;
+    async fn purge(&self, question_id: &QuestionId) -> Result<(), CoreError> {
+        let question_id = question_id.0;
         self.db
             .get()
             .await
-            .unwrap()
+            .map_err(pool_error)?
             .interact(move |conn| {
-                let _ = delete(questions.filter(id.eq(question_id)))
-                    .execute(conn)
-                    .map_err(|err| match err {
-                        diesel::result::Error::NotFound => CoreError::NotFound,
-                        _ => CoreError::InternalError(err.into()),
-                    })?;
-
-                Ok(())
+                let purged = delete(
+                    questions
+                        .filter(id.eq(question_id))
+                        .filter(deleted_at.is_not_null()),
+                )
+                .execute(conn)
+                .map_err(query_error)?;
+
+                match purged {
+                    0 => Err(missing_or_live(conn, question_id)),
+                    _ => Ok(()),
+                }
             })
             .await
-            .unwrap()
+            .map_err(interact_error)?
     }
 
     async fn get(&self, question_id: &QuestionId) -> Result<QuestionEntity, CoreError> {
-        let question_id: i32 = question_id.to_string().parse()?;
+        let question_id = question_id.0;
         self.db
             .get()
             .await
-            .unwrap()
+            .map_err(pool_error)?
             .interact(move |conn| {
                 let response = questions
                     .select(QuestionModel::as_select())
                     .find(question_id)
+                    .filter(deleted_at.is_null())
                     .first(conn)
-                    .map_err(|err| match err {
-                        diesel::result::Error::NotFound => CoreError::NotFound,
-                        _ => CoreError::InternalError(err.into()),
-                    })?
+                    .map_err(query_error)?
                     .into();
 
                 Ok(response)
             })
             .await
-            .unwrap()
+            .map_err(interact_error)?
     }
 
     async fn list(
         &self,
-        _question_filter: &QuestionFilter,
-    ) -> Result<Vec<QuestionEntity>, CoreError> {
+        question_filter: &QuestionFilter,
+    ) -> Result<PageEntity<QuestionEntity>, CoreError> {
+        let page_size = question_filter.pagination.limit();
+        let offset = i64::try_from(question_filter.pagination.offset())
+            .map_err(|err| CoreError::InternalError(err.into()))?;
+        // Fetch one extra row to find out whether another page follows.
+        let limit =
+            i64::try_from(page_size + 1).map_err(|err| CoreError::InternalError(err.into()))?;
+        let sort = question_filter.pagination.sort.clone().unwrap_or_default();
+        let cursor_predicate = question_filter
+            .pagination
+            .cursor
+            .as_ref()
+            .map(after_cursor)
+            .transpose()?;
+        let query = filtered_questions(question_filter);
+
+        self.db
+            .get()
+            .await
+            .map_err(pool_error)?
+            .interact(move |conn| {
+                let mut query = query.select(QuestionModel::as_select());
+                if let Some(predicate) = cursor_predicate {
+                    query = query.filter(predicate);
+                }
+
+                for key in &sort {
+                    query = match (key.field.as_str(), key.direction) {
+                        ("id", SortDirection::Asc) => query.then_order_by(id.asc()),
+                        ("id", SortDirection::Desc) => query.then_order_by(id.desc()),
+                        ("title", SortDirection::Asc) => {
+                            query.then_order_by(title_bytewise().asc())
+                        }
+                        ("title", SortDirection::Desc) => {
+                            query.then_order_by(title_bytewise().desc())
+                        }
+                        ("created_on", SortDirection::Asc) => query.then_order_by(created_on.asc()),
+                        ("created_on", SortDirection::Desc) => {
+                            query.then_order_by(created_on.desc())
+                        }
+                        (field, _) => {
+                            return Err(CoreError::InvalidParameter(format!(
+                                "sort: `{field}` is not sortable"
+                            )))
+                        }
+                    };
+                }
+
+                let mut question_list = query
+                    .then_order_by(id.asc())
+                    .offset(offset)
+                    .limit(limit)
+                    .load::<QuestionModel>(conn)
+                    .map_err(query_error)?;
+
+                let next_cursor = if question_list.len() > page_size {
+                    question_list.truncate(page_size);
+                    question_list
+                        .last()
+                        .map(|question| cursor_of(question, &sort)?.encode())
+                        .transpose()?
+                } else {
+                    None
+                };
+
+                Ok(PageEntity {
+                    items: question_list
+                        .into_iter()
+                        .map(|l| l.into())
+                        .collect::<Vec<_>>(),
+                    next_cursor,
+                })
+            })
+            .await
+            .map_err(interact_error)?
+    }
+
+    async fn search(
+        &self,
+        question_filter: &QuestionFilter,
+    ) -> Result<PageEntity<QuestionEntity>, CoreError> {
+        let Some(terms) = question_filter.search.clone() else {
+            return self.list(question_filter).await;
+        };
+        let offset = i64::try_from(question_filter.pagination.offset())
+            .map_err(|err| CoreError::InternalError(err.into()))?;
+        let limit = i64::try_from(question_filter.pagination.limit())
+            .map_err(|err| CoreError::InternalError(err.into()))?;
+        let query = filtered_questions(question_filter);
+
         self.db
             .get()
             .await
-            .unwrap()
+            .map_err(pool_error)?
             .interact(move |conn| {
-                let question_list = questions
+                let rank = ts_rank(
+                    search_vector,
+                    websearch_to_tsquery_with_search_config(SEARCH_CONFIGURATION, terms),
+                );
+                let question_list = query
                     .select(QuestionModel::as_select())
-                    .load(conn)
-                    .map_err(|err| match err {
-                        diesel::result::Error::NotFound => CoreError::NotFound,
-                        _ => CoreError::InternalError(err.into()),
-                    })?;
-
-                Ok(question_list
-                    .into_iter()
-                    .map(|l| l.into())
-                    .collect::<Vec<_>>())
+                    .order((rank.desc(), id.asc()))
+                    .offset(offset)
+                    .limit(limit)
+                    .load::<QuestionModel>(conn)
+                    .map_err(query_error)?;
+
+                Ok(PageEntity {
+                    items: question_list
+                        .into_iter()
+                        .map(|l| l.into())
+                        .collect::<Vec<_>>(),
+                    next_cursor: None,
+                })
+            })
+            .await
+            .map_err(interact_error)?
+    }
+
+    async fn count(&self, question_filter: &QuestionFilter) -> Result<usize, CoreError> {
+        let query = filtered_questions(question_filter);
+        self.db
+            .get()
+            .await
+            .map_err(pool_error)?
+            .interact(move |conn| {
+                let total = query.count().get_result::<i64>(conn).map_err(query_error)?;
+
+                usize::try_from(total).map_err(|err| CoreError::InternalError(err.into()))
             })
             .await
-            .unwrap()
+            .map_err(interact_error)?
     }
 }
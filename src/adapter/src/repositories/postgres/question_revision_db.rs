@@ -0,0 +1,75 @@
+use async_trait::async_trait;
+use deadpool_diesel::postgres::Pool;
+use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl, SelectableHelper};
+
+use rust_core::common::errors::CoreError;
+use rust_core::entities::question::QuestionId;
+use rust_core::entities::question_revision::QuestionRevisionEntity;
+use rust_core::ports::question_revision::QuestionRevisionPort;
+
+use crate::repositories::postgres::errors::{interact_error, pool_error, query_error};
+use crate::repositories::postgres::models::question_revision::QuestionRevisionModel;
+use crate::repositories::postgres::schema::question_revisions::dsl::{
+    question_id as revision_question_id, question_revisions, revision,
+};
+
+#[derive(Clone)]
+pub struct QuestionRevisionDBRepository {
+    pub db: Pool,
+}
+
+impl QuestionRevisionDBRepository {
+    pub fn new(db: Pool) -> Self {
+        QuestionRevisionDBRepository { db }
+    }
+}
+
+#[async_trait]
+impl QuestionRevisionPort for QuestionRevisionDBRepository {
+    async fn list(
+        &self,
+        question_id: &QuestionId,
+    ) -> Result<Vec<QuestionRevisionEntity>, CoreError> {
+        let question_id = question_id.0;
+        self.db
+            .get()
+            .await
+            .map_err(pool_error)?
+            .interact(move |conn| {
+                let revision_list = question_revisions
+                    .select(QuestionRevisionModel::as_select())
+                    .filter(revision_question_id.eq(question_id))
+                    .order(revision.asc())
+                    .load::<QuestionRevisionModel>(conn)
+                    .map_err(query_error)?;
+
+                Ok(revision_list.into_iter().map(|r| r.into()).collect())
+            })
+            .await
+            .map_err(interact_error)?
+    }
+
+    async fn get(
+        &self,
+        question_id: &QuestionId,
+        revision_number: i32,
+    ) -> Result<QuestionRevisionEntity, CoreError> {
+        let question_id = question_id.0;
+        self.db
+            .get()
+            .await
+            .map_err(pool_error)?
+            .interact(move |conn| {
+                let response = question_revisions
+                    .select(QuestionRevisionModel::as_select())
+                    .find((question_id, revision_number))
+                    .first(conn)
+                    .map_err(query_error)?
+                    .into();
+
+                Ok(response)
+            })
+            .await
+            .map_err(interact_error)?
+    }
+}
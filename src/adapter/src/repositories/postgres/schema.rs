@@ -1,12 +1,69 @@
 // @generated automatically by Diesel CLI.
 
 diesel::table! {
+    answers (id) {
+        id -> Uuid,
+        question_id -> Uuid,
+        body -> Text,
+        #[max_length = 255]
+        source_model -> Nullable<Varchar>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    question_revisions (question_id, revision) {
+        question_id -> Uuid,
+        revision -> Int4,
+        #[max_length = 255]
+        title -> Varchar,
+        content -> Text,
+        tags -> Nullable<Array<Nullable<Text>>>,
+        created_on -> Timestamp,
+    }
+}
+
+diesel::table! {
+    question_tags (question_id, tag_id) {
+        question_id -> Uuid,
+        tag_id -> Int4,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use diesel_full_text_search::Tsvector;
+
     questions (id) {
-        id -> Serial,
+        id -> Uuid,
         #[max_length = 255]
         title -> Varchar,
         content -> Text,
         tags -> Nullable<Array<Nullable<Text>>>,
         created_on -> Timestamp,
+        version -> Int4,
+        updated_at -> Timestamp,
+        deleted_at -> Nullable<Timestamp>,
+        search_vector -> Tsvector,
     }
 }
+
+diesel::table! {
+    tags (id) {
+        id -> Int4,
+        name -> Text,
+    }
+}
+
+diesel::joinable!(answers -> questions (question_id));
+diesel::joinable!(question_revisions -> questions (question_id));
+diesel::joinable!(question_tags -> questions (question_id));
+diesel::joinable!(question_tags -> tags (tag_id));
+
+diesel::allow_tables_to_appear_in_same_query!(
+    answers,
+    question_revisions,
+    question_tags,
+    questions,
+    tags,
+);
@@ -0,0 +1,58 @@
+use async_trait::async_trait;
+use deadpool_diesel::postgres::Pool;
+use diesel::dsl::{count_star, sql};
+use diesel::sql_types::Text;
+use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
+
+use rust_core::common::errors::CoreError;
+use rust_core::entities::tag::TagEntity;
+use rust_core::ports::tag::TagPort;
+
+use crate::repositories::postgres::errors::{interact_error, pool_error, query_error};
+use crate::repositories::postgres::schema::{question_tags, questions, tags};
+
+#[derive(Clone)]
+pub struct TagDBRepository {
+    pub db: Pool,
+}
+
+impl TagDBRepository {
+    pub fn new(db: Pool) -> Self {
+        TagDBRepository { db }
+    }
+}
+
+#[async_trait]
+impl TagPort for TagDBRepository {
+    async fn list(&self) -> Result<Vec<TagEntity>, CoreError> {
+        self.db
+            .get()
+            .await
+            .map_err(pool_error)?
+            .interact(move |conn| {
+                let tag_list = tags::table
+                    .inner_join(question_tags::table.inner_join(questions::table))
+                    .filter(questions::deleted_at.is_null())
+                    .group_by(tags::name)
+                    .select((tags::name, count_star()))
+                    .order((
+                        count_star().desc(),
+                        // Names compared byte by byte, matching how Rust orders strings.
+                        sql::<Text>(r#"tags.name COLLATE "C""#).asc(),
+                    ))
+                    .load::<(String, i64)>(conn)
+                    .map_err(query_error)?;
+
+                tag_list
+                    .into_iter()
+                    .map(|(name, count)| {
+                        let count = usize::try_from(count)
+                            .map_err(|err| CoreError::InternalError(err.into()))?;
+                        Ok(TagEntity { name, count })
+                    })
+                    .collect()
+            })
+            .await
+            .map_err(interact_error)?
+    }
+}
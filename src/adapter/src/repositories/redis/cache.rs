@@ -1,11 +1,68 @@
 use std::time::Duration;
 
 use async_trait::async_trait;
-use redis_async::{client, resp::RespValue, resp_array};
+use redis_async::{client, error, resp::RespValue, resp_array};
 
 use rust_core::{common::errors::CoreError, ports::cache::CachePort};
 
+/// Sets every key in `KEYS` to the value in `ARGV` after the expiration in `ARGV[1]`, atomically.
+const MSET_PX_SCRIPT: &str = "for i = 1, #KEYS do \
+    redis.call('SET', KEYS[i], ARGV[i + 1], 'PX', ARGV[1]) \
+end \
+return #KEYS";
+
+/// Number of keys `delete_by_pattern` asks for and removes at a time.
+const SCAN_COUNT: usize = 100;
+
+/// Maps a failed command to `CoreError::InternalError`, keeping what redis replied out of the
+/// message shown to clients.
+fn redis_error(err: error::Error) -> CoreError {
+    CoreError::InternalError(err.into())
+}
+
+/// Maps a failed `INCRBY` on `key`, reporting a value that is not an integer or a sum that would
+/// overflow as `CoreError::InvalidParameter`, like `InMemoryCache` does.
+fn incr_error(key: &str, err: error::Error) -> CoreError {
+    match &err {
+        error::Error::Remote(message)
+            if message.starts_with("WRONGTYPE")
+                || message.starts_with("ERR value is not an integer") =>
+        {
+            CoreError::InvalidParameter(format!("value of {} is not an integer", key))
+        }
+        error::Error::Remote(message)
+            if message.starts_with("ERR increment or decrement would overflow") =>
+        {
+            CoreError::InvalidParameter(format!("incrementing {} would overflow", key))
+        }
+        _ => redis_error(err),
+    }
+}
+
+fn unexpected_response(expected: &str, response: RespValue) -> CoreError {
+    CoreError::UnexpectedResponse(format!("Expect `{}` but found {:?}", expected, response))
+}
+
+/// Milliseconds redis is given for `expiration`, at least one as zero is rejected.
+fn millis(expiration: Duration) -> String {
+    expiration.as_millis().max(1).to_string()
+}
+
+/// Escapes the glob characters in `prefix` so that it only matches keys starting with it.
+fn prefix_pattern(prefix: &str) -> String {
+    let mut pattern = String::with_capacity(prefix.len() + 1);
+    for c in prefix.chars() {
+        if matches!(c, '*' | '?' | '[' | ']' | '\\') {
+            pattern.push('\\');
+        }
+        pattern.push(c);
+    }
+    pattern.push('*');
+    pattern
+}
+
 /// Represents a Redis cache implementation.
+#[derive(Clone)]
 pub struct RedisCache {
     client: client::PairedConnection,
 }
@@ -27,6 +84,22 @@ impl RedisCache {
             .map_err(|err| CoreError::InternalError(err.into()))
             .map(|c| Self { client: c })
     }
+
+    /// Publishes `message` to the subscribers of `channel`.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing the number of subscribers that received the message.
+    pub async fn publish(&self, channel: &str, message: &str) -> Result<usize, CoreError> {
+        self.client
+            .send(resp_array!["PUBLISH", channel, message])
+            .await
+            .map_err(redis_error)
+            .and_then(|resp| match resp {
+                RespValue::Integer(receivers) => Ok(receivers as usize),
+                response => Err(unexpected_response("RespValue::Integer", response)),
+            })
+    }
 }
 
 #[async_trait]
@@ -44,7 +117,7 @@ impl CachePort for RedisCache {
         self.client
             .send(resp_array!["GET", key])
             .await
-            .map_err(|e| CoreError::InternalError(e.into()))
+            .map_err(redis_error)
             .and_then(|r| match r {
                 RespValue::BulkString(data) => Ok(String::from_utf8_lossy(&data).to_string()),
                 _ => Err(CoreError::NotFound),
@@ -70,12 +143,12 @@ impl CachePort for RedisCache {
     ) -> Result<(), CoreError> {
         let args = vec!["SET", &key, &value];
         let expiration = expiration
-            .map(|r| vec!["EX".to_string(), r.as_secs().to_string()])
+            .map(|exp| vec!["PX".to_string(), millis(exp)])
             .unwrap_or_default();
         self.client
             .send(resp_array![].append(args).append(expiration))
             .await
-            .map_err(|e| CoreError::InternalError(e.into()))
+            .map_err(redis_error)
             .and_then(|r| match r {
                 RespValue::SimpleString(s) if s == "OK" => Ok(()),
                 _ => Err(CoreError::NotFound),
@@ -91,11 +164,11 @@ impl CachePort for RedisCache {
     /// # Returns
     ///
     /// Returns a `Result` where `Ok(())` indicates success (key was removed) and `Err(CoreError)` indicates failure.
-    async fn del(&mut self, key: &str) -> Result<(), CoreError> {
+    async fn del(&self, key: &str) -> Result<(), CoreError> {
         self.client
             .send(resp_array!["DEL", key])
             .await
-            .map_err(|e| CoreError::InternalError(e.into()))
+            .map_err(redis_error)
             .and_then(|resp| match resp {
                 RespValue::Integer(num_deleted) => {
                     if num_deleted > 0 {
@@ -104,10 +177,177 @@ impl CachePort for RedisCache {
                         Err(CoreError::NotFound)
                     }
                 }
-                response => Err(CoreError::UnexpectedResponse(format!(
-                    "Expect `RespValue::Integer` but found {:?}",
-                    response
-                ))),
+                response => Err(unexpected_response("RespValue::Integer", response)),
+            })
+    }
+
+    /// Retrieves the values of several keys with a single `MGET`.
+    async fn mget(&self, keys: &[&str]) -> Result<Vec<Option<String>>, CoreError> {
+        if keys.is_empty() {
+            return Ok(vec![]);
+        }
+        let response = self
+            .client
+            .send(resp_array!["MGET"].append(keys.iter().copied()))
+            .await
+            .map_err(redis_error)?;
+        match response {
+            RespValue::Array(values) => Ok(values
+                .into_iter()
+                .map(|value| match value {
+                    RespValue::BulkString(data) => Some(String::from_utf8_lossy(&data).to_string()),
+                    _ => None,
+                })
+                .collect()),
+            response => Err(unexpected_response("RespValue::Array", response)),
+        }
+    }
+
+    /// Sets several key-value pairs with a single `MSET`, or a script setting each of them when
+    /// they expire, which redis runs atomically as well.
+    async fn mset(
+        &self,
+        entries: &[(&str, &str)],
+        expiration: Option<Duration>,
+    ) -> Result<(), CoreError> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+        let command = match expiration {
+            None => {
+                resp_array!["MSET"].append(entries.iter().flat_map(|(key, value)| [*key, *value]))
+            }
+            Some(expiration) => resp_array!["EVAL", MSET_PX_SCRIPT, entries.len().to_string()]
+                .append(entries.iter().map(|(key, _)| *key))
+                .append([millis(expiration)])
+                .append(entries.iter().map(|(_, value)| *value)),
+        };
+        self.client
+            .send::<RespValue>(command)
+            .await
+            .map(|_| ())
+            .map_err(redis_error)
+    }
+
+    async fn exists(&self, key: &str) -> Result<bool, CoreError> {
+        self.client
+            .send(resp_array!["EXISTS", key])
+            .await
+            .map_err(redis_error)
+            .and_then(|resp| match resp {
+                RespValue::Integer(found) => Ok(found > 0),
+                response => Err(unexpected_response("RespValue::Integer", response)),
+            })
+    }
+
+    async fn ttl(&self, key: &str) -> Result<Option<Duration>, CoreError> {
+        self.client
+            .send(resp_array!["PTTL", key])
+            .await
+            .map_err(redis_error)
+            .and_then(|resp| match resp {
+                RespValue::Integer(-2) => Err(CoreError::NotFound),
+                RespValue::Integer(-1) => Ok(None),
+                RespValue::Integer(millis) => Ok(Some(Duration::from_millis(millis as u64))),
+                response => Err(unexpected_response("RespValue::Integer", response)),
+            })
+    }
+
+    async fn expire(&self, key: &str, expiration: Duration) -> Result<(), CoreError> {
+        self.client
+            .send(resp_array!["PEXPIRE", key, millis(expiration)])
+            .await
+            .map_err(redis_error)
+            .and_then(|resp| match resp {
+                RespValue::Integer(1) => Ok(()),
+                RespValue::Integer(_) => Err(CoreError::NotFound),
+                response => Err(unexpected_response("RespValue::Integer", response)),
+            })
+    }
+
+    async fn incr_by(&self, key: &str, delta: i64) -> Result<i64, CoreError> {
+        self.client
+            .send(resp_array!["INCRBY", key, delta.to_string()])
+            .await
+            .map_err(|err| incr_error(key, err))
+            .and_then(|resp| match resp {
+                RespValue::Integer(value) => Ok(value),
+                response => Err(unexpected_response("RespValue::Integer", response)),
             })
     }
+
+    async fn set_if_absent(
+        &self,
+        key: &str,
+        value: &str,
+        expiration: Option<Duration>,
+    ) -> Result<bool, CoreError> {
+        let expiration = expiration
+            .map(|exp| vec!["PX".to_string(), millis(exp)])
+            .unwrap_or_default();
+        self.client
+            .send(resp_array!["SET", key, value, "NX"].append(expiration))
+            .await
+            .map_err(redis_error)
+            .and_then(|resp| match resp {
+                RespValue::SimpleString(s) if s == "OK" => Ok(true),
+                RespValue::Nil => Ok(false),
+                response => Err(unexpected_response("RespValue::SimpleString", response)),
+            })
+    }
+
+    /// Removes the keys starting with `prefix`, scanning for them a batch at a time.
+    ///
+    /// Unlike the other operations this is not atomic: keys added while it runs may be left.
+    async fn delete_by_pattern(&self, prefix: &str) -> Result<usize, CoreError> {
+        let pattern = prefix_pattern(prefix);
+        let mut cursor = "0".to_string();
+        let mut deleted = 0;
+        loop {
+            let response = self
+                .client
+                .send(resp_array![
+                    "SCAN",
+                    cursor.as_str(),
+                    "MATCH",
+                    pattern.as_str(),
+                    "COUNT",
+                    SCAN_COUNT.to_string()
+                ])
+                .await
+                .map_err(redis_error)?;
+            let (next_cursor, keys) = match response {
+                RespValue::Array(mut reply) if reply.len() == 2 => {
+                    match (reply.remove(0), reply.remove(0)) {
+                        (RespValue::BulkString(next_cursor), RespValue::Array(keys)) => {
+                            (String::from_utf8_lossy(&next_cursor).to_string(), keys)
+                        }
+                        (next_cursor, keys) => {
+                            return Err(unexpected_response(
+                                "cursor and keys",
+                                RespValue::Array(vec![next_cursor, keys]),
+                            ))
+                        }
+                    }
+                }
+                response => return Err(unexpected_response("RespValue::Array", response)),
+            };
+
+            if !keys.is_empty() {
+                let response = self
+                    .client
+                    .send(resp_array!["DEL"].append(keys))
+                    .await
+                    .map_err(redis_error)?;
+                match response {
+                    RespValue::Integer(removed) => deleted += removed as usize,
+                    response => return Err(unexpected_response("RespValue::Integer", response)),
+                }
+            }
+            if next_cursor == "0" {
+                return Ok(deleted);
+            }
+            cursor = next_cursor;
+        }
+    }
 }
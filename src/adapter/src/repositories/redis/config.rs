@@ -0,0 +1,10 @@
+use serde::Deserialize;
+
+/// Represents redis configuration.
+#[derive(Deserialize, Debug, Clone)]
+pub struct RedisConfig {
+    /// Host for the redis.
+    pub host: String,
+    /// Port number for the redis.
+    pub port: u16,
+}
@@ -1 +1,3 @@
 pub mod cache;
+pub mod config;
+pub mod tiered_cache;
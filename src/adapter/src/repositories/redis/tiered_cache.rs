@@ -0,0 +1,357 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures_util::future::join_all;
+use futures_util::StreamExt;
+use redis_async::client::pubsub::{pubsub_connect, PubsubConnection, PubsubStream};
+use redis_async::resp::RespValue;
+use serde::Deserialize;
+use tokio::task::JoinHandle;
+use tokio::time::sleep;
+use tracing::warn;
+use uuid::Uuid;
+
+use rust_core::{common::errors::CoreError, ports::cache::CachePort};
+
+use crate::repositories::in_memory::cache::{InMemoryCache, InMemoryCacheConfig};
+use crate::repositories::redis::cache::RedisCache;
+use crate::repositories::redis::config::RedisConfig;
+
+/// Kind of invalidation dropping a single key.
+const INVALIDATE_KEY: &str = "key";
+
+/// Kind of invalidation dropping every key starting with a prefix.
+const INVALIDATE_PREFIX: &str = "prefix";
+
+/// Time between attempts to subscribe again after the subscription is lost.
+const RESUBSCRIBE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Represents tiered cache configuration.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TieredCacheConfig {
+    /// Capacity and eviction policy of the in-process tier.
+    #[serde(default)]
+    pub in_memory: InMemoryCacheConfig,
+    /// Seconds a value stays in the in-process tier at most.
+    #[serde(default = "default_ttl_secs")]
+    pub ttl_secs: u64,
+    /// Redis channel every replica publishes and receives invalidations on.
+    #[serde(default = "default_channel")]
+    pub channel: String,
+}
+
+fn default_ttl_secs() -> u64 {
+    60
+}
+
+fn default_channel() -> String {
+    "cache-invalidations".to_string()
+}
+
+impl Default for TieredCacheConfig {
+    fn default() -> Self {
+        Self {
+            in_memory: InMemoryCacheConfig::default(),
+            ttl_secs: default_ttl_secs(),
+            channel: default_channel(),
+        }
+    }
+}
+
+/// Stops the invalidation listener once the last clone of the cache is dropped.
+struct Subscription(JoinHandle<()>);
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// Represents a two-tier cache: an in-process `InMemoryCache` in front of a shared `RedisCache`.
+///
+/// Reads are served from the in-process tier when possible and fill it from redis otherwise, for
+/// no longer than the value has left to live in redis. Writes go to redis first, then to the
+/// in-process tier, and are announced on a redis channel so every other replica drops its
+/// in-process copy. An invalidation can still be missed, for instance when it races a read filling
+/// the in-process tier, so in-process values never live longer than `ttl_secs`. When the
+/// subscription is lost, the in-process tier is cleared once it is back.
+#[derive(Clone)]
+pub struct TieredCache {
+    l1: InMemoryCache,
+    l2: RedisCache,
+    channel: String,
+    origin: String,
+    ttl: Duration,
+    _subscription: Arc<Subscription>,
+}
+
+impl TieredCache {
+    /// Connects to redis and starts listening for invalidations from other replicas.
+    ///
+    /// # Arguments
+    ///
+    /// * `redis`: The redis server holding the shared tier and relaying invalidations.
+    /// * `config`: The in-process tier configuration.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` containing the `TieredCache` once it receives invalidations.
+    pub async fn new(redis: &RedisConfig, config: &TieredCacheConfig) -> Result<Self, CoreError> {
+        let l2 = RedisCache::new(&redis.host, redis.port).await?;
+        let pubsub = pubsub_connect(redis.host.clone(), redis.port)
+            .await
+            .map_err(|err| CoreError::InternalError(err.into()))?;
+        let messages = pubsub
+            .subscribe(&config.channel)
+            .await
+            .map_err(|err| CoreError::InternalError(err.into()))?;
+
+        let l1 = InMemoryCache::new(config.in_memory.clone());
+        let origin = Uuid::now_v7().to_string();
+        let listener = tokio::spawn(invalidate_on_messages(
+            l1.clone(),
+            pubsub,
+            config.channel.clone(),
+            origin.clone(),
+            messages,
+        ));
+        Ok(Self {
+            l1,
+            l2,
+            channel: config.channel.clone(),
+            origin,
+            ttl: Duration::from_secs(config.ttl_secs),
+            _subscription: Arc::new(Subscription(listener)),
+        })
+    }
+
+    /// Tells the other replicas to drop their in-process copy of `target`, a key or a prefix
+    /// depending on `kind`.
+    async fn invalidate(&self, kind: &str, target: &str) {
+        let message = format!("{} {} {}", self.origin, kind, target);
+        if let Err(err) = self.l2.publish(&self.channel, &message).await {
+            warn!("Failed to publish the invalidation of {}: {}", target, err);
+        }
+    }
+
+    /// Drops the in-process copy of `key` here and in the other replicas.
+    async fn forget(&self, key: &str) {
+        // The key may well not be cached in process
+        let _ = self.l1.del(key).await;
+        self.invalidate(INVALIDATE_KEY, key).await;
+    }
+
+    /// Caches `value` in process, dropping the previous copy when it does not fit.
+    async fn fill(&self, key: &str, value: &str, expiration: Duration) {
+        if let Err(err) = self.l1.set(key, value, Some(expiration)).await {
+            warn!("Failed to cache {} in process: {}", key, err);
+            let _ = self.l1.del(key).await;
+        }
+    }
+
+    /// How long a value expiring after `expiration` in redis stays in the in-process tier.
+    fn l1_expiration(&self, expiration: Option<Duration>) -> Duration {
+        expiration.map_or(self.ttl, |expiration| expiration.min(self.ttl))
+    }
+}
+
+/// Drops the keys other replicas invalidate from `l1`, subscribing again whenever the
+/// subscription is lost.
+async fn invalidate_on_messages(
+    l1: InMemoryCache,
+    pubsub: PubsubConnection,
+    channel: String,
+    origin: String,
+    mut messages: PubsubStream,
+) {
+    loop {
+        while let Some(message) = messages.next().await {
+            match message {
+                Ok(RespValue::BulkString(data)) => {
+                    let message = String::from_utf8_lossy(&data);
+                    let mut parts = message.splitn(3, ' ');
+                    match (parts.next(), parts.next(), parts.next()) {
+                        (Some(sender), _, _) if sender == origin => {}
+                        (_, Some(INVALIDATE_KEY), Some(key)) => {
+                            // The key may well not be cached in this replica
+                            let _ = l1.del(key).await;
+                        }
+                        (_, Some(INVALIDATE_PREFIX), Some(prefix)) => {
+                            let _ = l1.delete_by_pattern(prefix).await;
+                        }
+                        _ => warn!("Ignoring malformed cache invalidation: {}", message),
+                    }
+                }
+                Ok(message) => warn!("Ignoring unexpected cache invalidation: {:?}", message),
+                Err(err) => {
+                    warn!("Lost the cache invalidation subscription: {}", err);
+                    break;
+                }
+            }
+        }
+
+        messages = loop {
+            match pubsub.subscribe(&channel).await {
+                Ok(messages) => break messages,
+                Err(err) => {
+                    warn!("Failed to subscribe to cache invalidations: {}", err);
+                    sleep(RESUBSCRIBE_INTERVAL).await;
+                }
+            }
+        };
+        // Invalidations published while unsubscribed were missed.
+        l1.clear().await;
+    }
+}
+
+#[async_trait]
+impl CachePort for TieredCache {
+    /// Retrieves a value from the in-process tier, or from redis when it is not there, caching it
+    /// in process for no longer than it has left to live in redis.
+    ///
+    /// # Arguments
+    ///
+    /// * `key`: The key of the value to retrieve.
+    ///
+    /// # Returns
+    ///
+    /// Returns a Result containing the value associated with the key if found.
+    async fn get(&self, key: &str) -> Result<String, CoreError> {
+        if let Ok(value) = self.l1.get(key).await {
+            return Ok(value);
+        }
+        let value = self.l2.get(key).await?;
+        // The key may have expired in the meantime, leaving nothing to cache
+        if let Ok(expiration) = self.l2.ttl(key).await {
+            self.fill(key, &value, self.l1_expiration(expiration)).await;
+        }
+        Ok(value)
+    }
+
+    /// Sets a key-value pair in both tiers and invalidates it in the other replicas.
+    ///
+    /// # Arguments
+    ///
+    /// * `key`: The key to set.
+    /// * `value`: The value to associate with the key.
+    /// * `expiration`: Optional expiration duration for the key-value pair.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` where `Ok(())` indicates success (key was set) and `Err(CoreError)` indicates failure.
+    async fn set(
+        &self,
+        key: &str,
+        value: &str,
+        expiration: Option<Duration>,
+    ) -> Result<(), CoreError> {
+        self.l2.set(key, value, expiration).await?;
+        self.fill(key, value, self.l1_expiration(expiration)).await;
+        self.invalidate(INVALIDATE_KEY, key).await;
+        Ok(())
+    }
+
+    /// Removes a key-value pair from both tiers and from the other replicas.
+    ///
+    /// # Arguments
+    ///
+    /// * `key`: The key to remove.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` where `Ok(())` indicates success (key was removed from redis) and `Err(CoreError)` indicates failure.
+    async fn del(&self, key: &str) -> Result<(), CoreError> {
+        let result = self.l2.del(key).await;
+        self.forget(key).await;
+        result
+    }
+
+    /// Retrieves the values found in process, and the others from redis with a single `MGET`,
+    /// caching those in process for no longer than they have left to live in redis.
+    async fn mget(&self, keys: &[&str]) -> Result<Vec<Option<String>>, CoreError> {
+        let mut values = self.l1.mget(keys).await?;
+        let missing: Vec<usize> = (0..keys.len()).filter(|&i| values[i].is_none()).collect();
+        if missing.is_empty() {
+            return Ok(values);
+        }
+
+        let missing_keys: Vec<&str> = missing.iter().map(|&i| keys[i]).collect();
+        let found = self.l2.mget(&missing_keys).await?;
+        let expirations = join_all(missing_keys.iter().zip(&found).map(
+            |(key, value)| async move {
+                match value {
+                    Some(_) => self.l2.ttl(key).await.ok(),
+                    None => None,
+                }
+            },
+        ))
+        .await;
+        for ((i, value), expiration) in missing.into_iter().zip(found).zip(expirations) {
+            // A key expiring in the meantime leaves nothing to cache
+            if let (Some(value), Some(expiration)) = (&value, expiration) {
+                self.fill(keys[i], value, self.l1_expiration(expiration))
+                    .await;
+            }
+            values[i] = value;
+        }
+        Ok(values)
+    }
+
+    async fn mset(
+        &self,
+        entries: &[(&str, &str)],
+        expiration: Option<Duration>,
+    ) -> Result<(), CoreError> {
+        self.l2.mset(entries, expiration).await?;
+        for (key, value) in entries {
+            self.fill(key, value, self.l1_expiration(expiration)).await;
+            self.invalidate(INVALIDATE_KEY, key).await;
+        }
+        Ok(())
+    }
+
+    /// Checks redis, since an in-process copy may outlive the key when an invalidation is missed.
+    async fn exists(&self, key: &str) -> Result<bool, CoreError> {
+        self.l2.exists(key).await
+    }
+
+    /// Retrieves the time to live of the key in redis.
+    async fn ttl(&self, key: &str) -> Result<Option<Duration>, CoreError> {
+        self.l2.ttl(key).await
+    }
+
+    async fn expire(&self, key: &str, expiration: Duration) -> Result<(), CoreError> {
+        self.l2.expire(key, expiration).await?;
+        self.forget(key).await;
+        Ok(())
+    }
+
+    /// Adds to the counter in redis, leaving it out of the in-process tier as it keeps changing.
+    async fn incr_by(&self, key: &str, delta: i64) -> Result<i64, CoreError> {
+        let value = self.l2.incr_by(key, delta).await?;
+        self.forget(key).await;
+        Ok(value)
+    }
+
+    async fn set_if_absent(
+        &self,
+        key: &str,
+        value: &str,
+        expiration: Option<Duration>,
+    ) -> Result<bool, CoreError> {
+        if !self.l2.set_if_absent(key, value, expiration).await? {
+            return Ok(false);
+        }
+        self.fill(key, value, self.l1_expiration(expiration)).await;
+        self.invalidate(INVALIDATE_KEY, key).await;
+        Ok(true)
+    }
+
+    async fn delete_by_pattern(&self, prefix: &str) -> Result<usize, CoreError> {
+        let deleted = self.l2.delete_by_pattern(prefix).await?;
+        self.l1.delete_by_pattern(prefix).await?;
+        self.invalidate(INVALIDATE_PREFIX, prefix).await;
+        Ok(deleted)
+    }
+}
@@ -0,0 +1,121 @@
+use rust_core::common::errors::CoreError;
+use tonic::{Code, Status};
+
+/// HTTP status code a `CoreError` is reported with.
+pub fn http_status(err: &CoreError) -> u16 {
+    match err {
+        CoreError::ParseError(_) => 400,
+        CoreError::MissingParameters => 400,
+        CoreError::InvalidParameter(_) => 400,
+        CoreError::Unauthorized(_) => 401,
+        CoreError::Forbidden(_) => 403,
+        CoreError::NotFound => 404,
+        CoreError::Conflict(_) => 409,
+        CoreError::Validation(_) => 422,
+        CoreError::RateLimited(_) => 429,
+        CoreError::IOError(_) => 500,
+        CoreError::InternalError(_) => 500,
+        CoreError::UnexpectedResponse(_) => 502,
+        CoreError::Unavailable(_) => 503,
+        CoreError::Timeout(_) => 504,
+    }
+}
+
+/// gRPC code a `CoreError` is reported with.
+pub fn grpc_code(err: &CoreError) -> Code {
+    match err {
+        CoreError::ParseError(_) => Code::InvalidArgument,
+        CoreError::MissingParameters => Code::InvalidArgument,
+        CoreError::InvalidParameter(_) => Code::InvalidArgument,
+        CoreError::Validation(_) => Code::InvalidArgument,
+        CoreError::Unauthorized(_) => Code::Unauthenticated,
+        CoreError::Forbidden(_) => Code::PermissionDenied,
+        CoreError::NotFound => Code::NotFound,
+        CoreError::Conflict(_) => Code::Aborted,
+        CoreError::RateLimited(_) => Code::ResourceExhausted,
+        CoreError::IOError(_) => Code::Internal,
+        CoreError::InternalError(_) => Code::Internal,
+        CoreError::UnexpectedResponse(_) => Code::Unknown,
+        CoreError::Unavailable(_) => Code::Unavailable,
+        CoreError::Timeout(_) => Code::DeadlineExceeded,
+    }
+}
+
+/// gRPC status a `CoreError` is reported with, carrying its message.
+pub fn grpc_status(err: &CoreError) -> Status {
+    Status::new(grpc_code(err), err.to_string())
+}
+
+/// `CoreError` a gRPC status received from another service is turned into.
+///
+/// Codes without a close `CoreError` counterpart become `CoreError::UnexpectedResponse`.
+pub fn from_grpc_status(status: &Status) -> CoreError {
+    let message = status.message().to_string();
+    match status.code() {
+        Code::InvalidArgument => CoreError::InvalidParameter(message),
+        Code::Unauthenticated => CoreError::Unauthorized(message),
+        Code::PermissionDenied => CoreError::Forbidden(message),
+        Code::NotFound => CoreError::NotFound,
+        Code::Aborted | Code::AlreadyExists => CoreError::Conflict(message),
+        Code::ResourceExhausted => CoreError::RateLimited(message),
+        Code::Unavailable => CoreError::Unavailable(message),
+        Code::DeadlineExceeded => CoreError::Timeout(message),
+        _ => CoreError::UnexpectedResponse(format!("{}: {}", status.code(), message)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_http_status() {
+        assert_eq!(http_status(&CoreError::NotFound), 404);
+        assert_eq!(http_status(&CoreError::Conflict("stale".to_string())), 409);
+        assert_eq!(
+            http_status(&CoreError::Unauthorized("no token".to_string())),
+            401
+        );
+        assert_eq!(
+            http_status(&CoreError::Forbidden("admin only".to_string())),
+            403
+        );
+        assert_eq!(
+            http_status(&CoreError::RateLimited("slow down".to_string())),
+            429
+        );
+        assert_eq!(
+            http_status(&CoreError::Unavailable("down".to_string())),
+            503
+        );
+        assert_eq!(
+            http_status(&CoreError::Timeout("too slow".to_string())),
+            504
+        );
+    }
+
+    #[test]
+    fn test_grpc_status_round_trip() {
+        let errors = [
+            CoreError::InvalidParameter("q".to_string()),
+            CoreError::Unauthorized("no token".to_string()),
+            CoreError::Forbidden("admin only".to_string()),
+            CoreError::NotFound,
+            CoreError::Conflict("stale".to_string()),
+            CoreError::RateLimited("slow down".to_string()),
+            CoreError::Unavailable("down".to_string()),
+            CoreError::Timeout("too slow".to_string()),
+        ];
+        for err in errors {
+            let status = grpc_status(&err);
+            assert_eq!(status.code(), grpc_code(&err));
+            assert_eq!(grpc_code(&from_grpc_status(&status)), grpc_code(&err));
+        }
+
+        let status = Status::internal("boom");
+        assert!(matches!(
+            from_grpc_status(&status),
+            CoreError::UnexpectedResponse(_)
+        ));
+    }
+}
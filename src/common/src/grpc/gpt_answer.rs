@@ -3,6 +3,7 @@
 /// This module includes generated gRPC service definitions for answering questions using GPT models.
 /// The `tonic::include_proto!` macro is used to include the protobuf definitions, enabling easy
 /// integration of gRPC services into Rust code.
+#[allow(clippy::module_inception)]
 pub mod gpt_answer {
     // Include the protobuf definitions for the gpt_answer service.
     tonic::include_proto!("gpt_answer");
@@ -1,3 +1,4 @@
+pub mod errors;
 pub mod grpc;
 pub mod kill_signals;
 pub mod loggers;
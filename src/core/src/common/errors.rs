@@ -1,5 +1,7 @@
 use anyhow::Error;
 
+use crate::common::validation::ValidationErrors;
+
 #[derive(thiserror::Error, Debug)]
 pub enum CoreError {
     #[error("parse error {0}")]
@@ -11,9 +13,33 @@ pub enum CoreError {
     #[error("missing parameters")]
     MissingParameters,
 
+    #[error("invalid parameter {0}")]
+    InvalidParameter(String),
+
     #[error("not found")]
     NotFound,
 
+    #[error("conflict {0}")]
+    Conflict(String),
+
+    #[error("validation failed {0}")]
+    Validation(ValidationErrors),
+
+    #[error("unauthorized {0}")]
+    Unauthorized(String),
+
+    #[error("forbidden {0}")]
+    Forbidden(String),
+
+    #[error("unavailable {0}")]
+    Unavailable(String),
+
+    #[error("timeout {0}")]
+    Timeout(String),
+
+    #[error("rate limited {0}")]
+    RateLimited(String),
+
     #[error("internal error {0}")]
     InternalError(#[from] Error),
 
@@ -1 +1,3 @@
 pub mod errors;
+pub mod typed_cache;
+pub mod validation;
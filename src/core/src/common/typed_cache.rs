@@ -0,0 +1,162 @@
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::common::errors::CoreError;
+use crate::ports::cache::CachePort;
+
+/// How a `TypedCache` turns values into the strings a `CachePort` stores.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CacheEncoding {
+    /// Stores values as JSON text.
+    #[default]
+    Json,
+    /// Stores values as base64 encoded bincode, which is more compact for large values.
+    Bincode,
+}
+
+impl CacheEncoding {
+    fn encode<T: Serialize>(&self, value: &T) -> Result<String, CoreError> {
+        match self {
+            CacheEncoding::Json => {
+                serde_json::to_string(value).map_err(|err| CoreError::InternalError(err.into()))
+            }
+            CacheEncoding::Bincode => bincode::serialize(value)
+                .map(|bytes| STANDARD.encode(bytes))
+                .map_err(|err| CoreError::InternalError(err.into())),
+        }
+    }
+
+    fn decode<T: DeserializeOwned>(&self, value: &str) -> Result<T, CoreError> {
+        let decoded = match self {
+            CacheEncoding::Json => serde_json::from_str(value).map_err(|err| err.to_string()),
+            CacheEncoding::Bincode => STANDARD
+                .decode(value)
+                .map_err(|err| err.to_string())
+                .and_then(|bytes| bincode::deserialize(&bytes).map_err(|err| err.to_string())),
+        };
+        decoded.map_err(|err| {
+            CoreError::UnexpectedResponse(format!("cached value cannot be decoded: {}", err))
+        })
+    }
+}
+
+/// Stores values of type `T` in a `CachePort`, serializing them with serde.
+///
+/// Keys are prefixed with a namespace and a version, as `<namespace>:v<version>:<key>`, so
+/// caches of different types can share a store, and bumping the version after changing `T`
+/// leaves the entries written in the old shape behind instead of failing to decode them.
+pub struct TypedCache<T, C> {
+    cache: C,
+    namespace: String,
+    version: u32,
+    encoding: CacheEncoding,
+    value: PhantomData<fn() -> T>,
+}
+
+impl<T, C> TypedCache<T, C>
+where
+    T: Serialize + DeserializeOwned,
+    C: CachePort + Send + Sync,
+{
+    /// Creates a cache of JSON encoded values under version 1 of `namespace`.
+    pub fn new(cache: C, namespace: &str) -> Self {
+        TypedCache {
+            cache,
+            namespace: namespace.to_string(),
+            version: 1,
+            encoding: CacheEncoding::default(),
+            value: PhantomData,
+        }
+    }
+
+    pub fn with_version(self, version: u32) -> Self {
+        TypedCache { version, ..self }
+    }
+
+    pub fn with_encoding(self, encoding: CacheEncoding) -> Self {
+        TypedCache { encoding, ..self }
+    }
+
+    /// The key `key` is stored under in the underlying cache.
+    pub fn key(&self, key: &str) -> String {
+        format!("{}:v{}:{}", self.namespace, self.version, key)
+    }
+
+    /// Retrieves the value stored under `key`, failing with `CoreError::NotFound` when there is
+    /// none and `CoreError::UnexpectedResponse` when it cannot be decoded as `T`.
+    pub async fn get(&self, key: &str) -> Result<T, CoreError> {
+        let value = self.cache.get(&self.key(key)).await?;
+        self.encoding.decode(&value)
+    }
+
+    /// Stores `value` under `key`, expiring it after `expiration` when given.
+    pub async fn set(
+        &self,
+        key: &str,
+        value: &T,
+        expiration: Option<Duration>,
+    ) -> Result<(), CoreError> {
+        let value = self.encoding.encode(value)?;
+        self.cache.set(&self.key(key), &value, expiration).await
+    }
+
+    /// Stores `value` under `key` unless a value is already stored there, returning whether it
+    /// was stored.
+    pub async fn set_if_absent(
+        &self,
+        key: &str,
+        value: &T,
+        expiration: Option<Duration>,
+    ) -> Result<bool, CoreError> {
+        let value = self.encoding.encode(value)?;
+        self.cache
+            .set_if_absent(&self.key(key), &value, expiration)
+            .await
+    }
+
+    /// Removes the value stored under `key`, failing with `CoreError::NotFound` when there is
+    /// none.
+    pub async fn del(&self, key: &str) -> Result<(), CoreError> {
+        let key = self.key(key);
+        self.cache.del(&key).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Answer {
+        body: String,
+        votes: u32,
+    }
+
+    #[test]
+    fn test_cache_encoding_round_trip() {
+        let answer = Answer {
+            body: "Box::pin it.".to_string(),
+            votes: 3,
+        };
+        for encoding in [CacheEncoding::Json, CacheEncoding::Bincode] {
+            let encoded = encoding.encode(&answer).unwrap();
+            assert_eq!(encoding.decode::<Answer>(&encoded).unwrap(), answer);
+        }
+        assert_eq!(
+            CacheEncoding::Json.encode(&answer).unwrap(),
+            r#"{"body":"Box::pin it.","votes":3}"#
+        );
+
+        match CacheEncoding::Bincode.decode::<Answer>("not base64!") {
+            Err(CoreError::UnexpectedResponse(_)) => {}
+            other => panic!("Expected UnexpectedResponse error, but got {:?}", other),
+        }
+    }
+}
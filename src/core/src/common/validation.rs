@@ -0,0 +1,92 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::common::errors::CoreError;
+
+/// Problems found while validating input, as messages keyed by the field they concern.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(transparent)]
+pub struct ValidationErrors(BTreeMap<String, Vec<String>>);
+
+impl ValidationErrors {
+    /// Records a problem with `field`.
+    pub fn add(&mut self, field: &str, message: impl Into<String>) {
+        self.0
+            .entry(field.to_string())
+            .or_default()
+            .push(message.into());
+    }
+
+    /// Whether no problem has been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Messages recorded for `field`, empty when the field is valid.
+    pub fn field(&self, field: &str) -> &[String] {
+        self.0.get(field).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Succeeds when no problem has been recorded, and fails with `CoreError::Validation`
+    /// otherwise.
+    pub fn into_result(self) -> Result<(), CoreError> {
+        match self.is_empty() {
+            true => Ok(()),
+            false => Err(CoreError::Validation(self)),
+        }
+    }
+}
+
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fields = self
+            .0
+            .iter()
+            .map(|(field, messages)| format!("{field}: {}", messages.join(", ")))
+            .collect::<Vec<_>>();
+        write!(f, "{}", fields.join("; "))
+    }
+}
+
+/// Input that can be checked before it is written.
+pub trait Validate {
+    /// Checks every field, failing with `CoreError::Validation` listing all the problems found.
+    fn validate(&self) -> Result<(), CoreError>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validation_errors() {
+        let mut errors = ValidationErrors::default();
+        assert!(errors.clone().into_result().is_ok());
+
+        errors.add("title", "must not be blank");
+        errors.add("tags", "at most 5 tags are allowed");
+        errors.add("title", "must be at most 255 characters");
+        assert_eq!(
+            errors.field("title"),
+            ["must not be blank", "must be at most 255 characters"]
+        );
+        assert!(errors.field("content").is_empty());
+        assert_eq!(
+            errors.to_string(),
+            "tags: at most 5 tags are allowed; title: must not be blank, must be at most 255 characters"
+        );
+        assert_eq!(
+            serde_json::to_value(&errors).unwrap(),
+            serde_json::json!({
+                "tags": ["at most 5 tags are allowed"],
+                "title": ["must not be blank", "must be at most 255 characters"]
+            })
+        );
+        assert!(matches!(
+            errors.into_result(),
+            Err(CoreError::Validation(_))
+        ));
+    }
+}
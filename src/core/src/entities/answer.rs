@@ -0,0 +1,130 @@
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::common::errors::CoreError;
+use crate::common::validation::{Validate, ValidationErrors};
+use crate::entities::entity::Entity;
+use crate::entities::question::QuestionId;
+
+/// Identifier for an answer.
+///
+/// Like question ids, answer ids are version 7 UUIDs, so they sort in creation order.
+#[derive(Debug, Serialize, Deserialize, Eq, Hash, PartialEq, PartialOrd, Ord, Clone)]
+pub struct AnswerId(pub Uuid);
+
+impl AnswerId {
+    /// Allocates a fresh, time ordered `AnswerId`.
+    pub fn generate() -> Self {
+        AnswerId(Uuid::now_v7())
+    }
+}
+
+impl FromStr for AnswerId {
+    type Err = CoreError;
+
+    /// Attempts to parse a string into an `AnswerId`, failing with
+    /// `CoreError::InvalidParameter` if the string is not a UUID.
+    fn from_str(id: &str) -> Result<Self, Self::Err> {
+        Uuid::parse_str(id)
+            .map(AnswerId)
+            .map_err(|err| CoreError::InvalidParameter(format!("answer id: {err}")))
+    }
+}
+
+impl fmt::Display for AnswerId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Represents an answer to a question, either written by a person or generated.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct AnswerEntity {
+    /// Identifier for the answer.
+    pub id: AnswerId,
+    /// Identifier of the question being answered.
+    pub question_id: QuestionId,
+    /// Text of the answer.
+    pub body: String,
+    /// Model that generated the answer, absent for answers written by a person.
+    pub source_model: Option<String>,
+    /// When the answer was stored.
+    pub created_at: DateTime<Utc>,
+}
+
+impl Entity<AnswerEntity> for AnswerEntity {}
+
+/// Represents the fields supplied to store an answer.
+///
+/// The identifier, question and timestamp are left out, since the repository fills them in.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct CreateAnswerEntity {
+    /// Text of the answer.
+    pub body: String,
+    /// Model that generated the answer, absent for answers written by a person.
+    #[serde(default)]
+    pub source_model: Option<String>,
+}
+
+impl CreateAnswerEntity {
+    /// Turns the payload into an `AnswerEntity` to `question_id` stored under `id` now.
+    pub fn into_entity(self, id: AnswerId, question_id: QuestionId) -> AnswerEntity {
+        AnswerEntity {
+            id,
+            question_id,
+            body: self.body,
+            source_model: self.source_model,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+impl Validate for CreateAnswerEntity {
+    fn validate(&self) -> Result<(), CoreError> {
+        let mut errors = ValidationErrors::default();
+        if self.body.trim().is_empty() {
+            errors.add("body", "must not be blank");
+        }
+        errors.into_result()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer_id_from_str() {
+        let id = AnswerId::from_str("0190A5C4-7D0E-7C3A-9F1E-4B2D8C6A5E10").unwrap();
+        assert_eq!(id.to_string(), "0190a5c4-7d0e-7c3a-9f1e-4b2d8c6a5e10");
+
+        match AnswerId::from_str("42") {
+            Err(CoreError::InvalidParameter(_)) => {}
+            other => panic!("Expected InvalidParameter error, but got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_create_answer_entity_validate() {
+        let answer = CreateAnswerEntity {
+            body: "Box::pin it.".to_string(),
+            source_model: None,
+        };
+        assert!(answer.validate().is_ok());
+
+        let answer = CreateAnswerEntity {
+            body: "\n".to_string(),
+            source_model: None,
+        };
+        match answer.validate() {
+            Err(CoreError::Validation(errors)) => {
+                assert_eq!(errors.field("body"), ["must not be blank"]);
+            }
+            other => panic!("Expected Validation error, but got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::common::errors::CoreError;
+use crate::entities::sort_entity::SortEntity;
+
+/// Value of a single sort key captured in a cursor.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum CursorValue {
+    Text(String),
+    Timestamp(DateTime<Utc>),
+}
+
+/// Position of the last item of a page, used for keyset pagination.
+///
+/// Clients only ever see the encoded form, which they hand back unchanged to fetch the next page.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CursorEntity {
+    /// Sort keys the page was produced with.
+    pub sort: Vec<SortEntity>,
+    /// Values of the sort keys on the last item, in the same order as `sort`.
+    pub values: Vec<CursorValue>,
+    /// Identifier of the last item, which breaks ties between equal sort values.
+    pub id: String,
+}
+
+impl CursorEntity {
+    /// Encodes the cursor into an opaque, URL safe string.
+    pub fn encode(&self) -> Result<String, CoreError> {
+        let json = serde_json::to_vec(self).map_err(|err| CoreError::InternalError(err.into()))?;
+        Ok(URL_SAFE_NO_PAD.encode(json))
+    }
+
+    /// Decodes a cursor previously produced by `encode`.
+    pub fn decode(raw: &str) -> Result<Self, CoreError> {
+        let malformed = || CoreError::InvalidParameter("cursor: malformed".to_string());
+        let json = URL_SAFE_NO_PAD.decode(raw).map_err(|_| malformed())?;
+        let cursor: CursorEntity = serde_json::from_slice(&json).map_err(|_| malformed())?;
+        if cursor.values.len() != cursor.sort.len() {
+            return Err(malformed());
+        }
+        Ok(cursor)
+    }
+
+    /// Ensures the cursor was issued for the same sort order as the current request.
+    pub fn ensure_sort(&self, sort: &[SortEntity]) -> Result<(), CoreError> {
+        if self.sort != sort {
+            return Err(CoreError::InvalidParameter(
+                "cursor: issued for a different sort order".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::sort_entity::SortDirection;
+
+    #[test]
+    fn test_cursor_entity_round_trip() {
+        let cursor = CursorEntity {
+            sort: vec![SortEntity {
+                field: "created_on".to_string(),
+                direction: SortDirection::Desc,
+            }],
+            values: vec![CursorValue::Timestamp(
+                DateTime::parse_from_rfc3339("2024-01-01T00:00:00.123456789Z")
+                    .unwrap()
+                    .with_timezone(&Utc),
+            )],
+            id: "42".to_string(),
+        };
+
+        let encoded = cursor.encode().unwrap();
+        assert!(encoded
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+        assert_eq!(CursorEntity::decode(&encoded).unwrap(), cursor);
+        assert!(cursor.ensure_sort(&cursor.sort).is_ok());
+        assert!(matches!(
+            cursor.ensure_sort(&[]),
+            Err(CoreError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn test_cursor_entity_decode_malformed() {
+        for raw in ["not a cursor", "e30", ""] {
+            match CursorEntity::decode(raw) {
+                Err(CoreError::InvalidParameter(_)) => {}
+                other => panic!("Expected InvalidParameter error, but got {:?}", other),
+            }
+        }
+    }
+}
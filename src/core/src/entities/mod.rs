@@ -1,5 +1,12 @@
+pub mod answer;
+pub mod cursor_entity;
 pub mod entity;
 pub mod filter_entity;
+pub mod page_entity;
 pub mod pagination_entity;
+pub mod principal;
 pub mod question;
 pub mod question_filter;
+pub mod question_revision;
+pub mod sort_entity;
+pub mod tag;
@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+
+/// A page of items returned by a list query.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct PageEntity<T> {
+    /// Items in the page.
+    pub items: Vec<T>,
+    /// Opaque cursor of the following page, absent on the last page.
+    pub next_cursor: Option<String>,
+}
@@ -3,6 +3,8 @@ use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 
 use crate::common::errors::CoreError;
+use crate::entities::cursor_entity::CursorEntity;
+use crate::entities::sort_entity::SortEntity;
 
 /// Represents pagination parameters.
 #[derive(Debug, Serialize, Deserialize)]
@@ -11,19 +13,73 @@ pub struct PaginationEntity {
     pub start: usize,
     /// End index for pagination.
     pub end: usize,
-    /// Optional sorting criteria.
-    pub sort: Option<Vec<String>>,
+    /// Optional sorting criteria, applied in order.
+    pub sort: Option<Vec<SortEntity>>,
+    /// Optional cursor; when set the page starts right after it and `start` is ignored.
+    pub cursor: Option<CursorEntity>,
+}
+
+impl Default for PaginationEntity {
+    /// Returns the first page of ten items, which is also what an empty query resolves to.
+    fn default() -> Self {
+        PaginationEntity {
+            start: 0,
+            end: 10,
+            sort: None,
+            cursor: None,
+        }
+    }
+}
+
+impl PaginationEntity {
+    /// Number of items to skip before the page starts, which is zero in cursor mode.
+    pub fn offset(&self) -> usize {
+        match self.cursor {
+            Some(_) => 0,
+            None => self.start,
+        }
+    }
+
+    /// Maximum number of items in the page.
+    pub fn limit(&self) -> usize {
+        self.end.saturating_sub(self.start)
+    }
+
+    /// Ensures every sort key names one of the `allowed` fields.
+    pub fn validate_sort(&self, allowed: &[&str]) -> Result<(), CoreError> {
+        let unknown = self
+            .sort
+            .iter()
+            .flatten()
+            .find(|sort| !allowed.contains(&sort.field.as_str()));
+        match unknown {
+            Some(sort) => Err(CoreError::InvalidParameter(format!(
+                "sort: `{}` is not one of {}",
+                sort.field,
+                allowed.join(", ")
+            ))),
+            None => Ok(()),
+        }
+    }
 }
 
 /// Implementation of the `TryFrom` trait to convert a HashMap into a `PaginationEntity`.
 ///
 /// This implementation allows converting a HashMap containing query parameters into a `PaginationEntity`.
-/// It attempts to parse the start and end pagination parameters from the HashMap and constructs a `PaginationEntity`
-/// instance. If parsing fails for any reason, it returns a `CoreError`.
+/// It attempts to parse the start and end pagination parameters, the comma separated `sort` keys and the opaque
+/// `cursor` from the HashMap and constructs a `PaginationEntity` instance. If parsing fails for any reason, or `end` lies before `start`, it returns a `CoreError`.
 impl TryFrom<HashMap<String, String>> for PaginationEntity {
     type Error = CoreError;
 
     fn try_from(query: HashMap<String, String>) -> Result<Self, CoreError> {
+        Self::try_from(&query)
+    }
+}
+
+impl TryFrom<&HashMap<String, String>> for PaginationEntity {
+    type Error = CoreError;
+
+    fn try_from(query: &HashMap<String, String>) -> Result<Self, CoreError> {
         let start = query
             .get("start")
             .unwrap_or(&"0".to_string())
@@ -32,10 +88,29 @@ impl TryFrom<HashMap<String, String>> for PaginationEntity {
             .get("end")
             .unwrap_or(&"10".to_string())
             .parse::<usize>()?;
+        if end < start {
+            return Err(CoreError::InvalidParameter(format!(
+                "end ({end}) must not be less than start ({start})"
+            )));
+        }
+        let sort = query
+            .get("sort")
+            .map(|raw| SortEntity::parse_list(raw))
+            .transpose()?
+            .filter(|sort| !sort.is_empty());
+        let cursor = query
+            .get("cursor")
+            .filter(|raw| !raw.is_empty())
+            .map(|raw| CursorEntity::decode(raw))
+            .transpose()?;
+        if let Some(cursor) = &cursor {
+            cursor.ensure_sort(sort.as_deref().unwrap_or_default())?;
+        }
         Ok(PaginationEntity {
             start,
             end,
-            sort: None,
+            sort,
+            cursor,
         })
     }
 }
@@ -43,6 +118,8 @@ impl TryFrom<HashMap<String, String>> for PaginationEntity {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::entities::cursor_entity::CursorValue;
+    use crate::entities::sort_entity::SortDirection;
 
     #[test]
     fn test_pagination_entity_from_query() {
@@ -81,4 +158,63 @@ mod tests {
             },
         }
     }
+
+    #[test]
+    fn test_pagination_entity_from_query_sort() {
+        let mut query_params = HashMap::new();
+        query_params.insert("sort".to_string(), "-created_on,title".to_string());
+
+        let pagination_entity = PaginationEntity::try_from(query_params).unwrap();
+        let sort = pagination_entity.sort.clone().unwrap();
+        assert_eq!(sort.len(), 2);
+        assert_eq!(sort[0].field, "created_on");
+        assert_eq!(sort[0].direction, SortDirection::Desc);
+        assert_eq!(sort[1].field, "title");
+        assert_eq!(sort[1].direction, SortDirection::Asc);
+
+        assert!(pagination_entity
+            .validate_sort(&["id", "title", "created_on"])
+            .is_ok());
+        match pagination_entity.validate_sort(&["id", "title"]) {
+            Err(CoreError::InvalidParameter(_)) => {}
+            other => panic!("Expected InvalidParameter error, but got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_pagination_entity_from_query_cursor() {
+        let cursor = CursorEntity {
+            sort: SortEntity::parse_list("-title").unwrap(),
+            values: vec![CursorValue::Text("Rust".to_string())],
+            id: "3".to_string(),
+        };
+        let mut query_params = HashMap::new();
+        query_params.insert("start".to_string(), "20".to_string());
+        query_params.insert("end".to_string(), "25".to_string());
+        query_params.insert("sort".to_string(), "-title".to_string());
+        query_params.insert("cursor".to_string(), cursor.encode().unwrap());
+
+        let pagination_entity = PaginationEntity::try_from(&query_params).unwrap();
+        assert_eq!(pagination_entity.cursor, Some(cursor));
+        assert_eq!(pagination_entity.offset(), 0);
+        assert_eq!(pagination_entity.limit(), 5);
+
+        // A cursor is only valid for the sort order it was issued for.
+        query_params.insert("sort".to_string(), "title".to_string());
+        match PaginationEntity::try_from(query_params) {
+            Err(CoreError::InvalidParameter(_)) => {}
+            other => panic!("Expected InvalidParameter error, but got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_pagination_entity_from_query_end_before_start() {
+        let mut query_params = HashMap::new();
+        query_params.insert("start".to_string(), "10".to_string());
+        query_params.insert("end".to_string(), "5".to_string());
+        match PaginationEntity::try_from(query_params) {
+            Err(CoreError::InvalidParameter(_)) => {}
+            other => panic!("Expected InvalidParameter error, but got {:?}", other),
+        }
+    }
 }
@@ -0,0 +1,75 @@
+use serde::Deserialize;
+
+use crate::common::errors::CoreError;
+
+/// Role granted to an authenticated caller. Roles are ordered, so a role grants everything the
+/// roles before it do.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Ord, PartialOrd)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    /// Regular user of the API.
+    Member,
+    /// Operator allowed to restore and purge deleted questions.
+    Admin,
+}
+
+/// Represents who a use case runs on behalf of.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Principal {
+    /// Caller that did not authenticate.
+    Anonymous,
+    /// Caller that authenticated as `subject`.
+    Authenticated { subject: String, role: Role },
+}
+
+impl Principal {
+    /// Checks that the principal was granted `role`.
+    ///
+    /// # Returns
+    ///
+    /// `CoreError::Unauthorized` for an anonymous caller, and `CoreError::Forbidden` for a caller
+    /// whose role is below `role`.
+    pub fn require(&self, role: Role) -> Result<(), CoreError> {
+        match self {
+            Principal::Anonymous => Err(CoreError::Unauthorized(
+                "authentication is required".to_string(),
+            )),
+            Principal::Authenticated {
+                role: granted,
+                subject,
+            } if *granted < role => Err(CoreError::Forbidden(format!(
+                "{subject} is not allowed to perform this operation"
+            ))),
+            Principal::Authenticated { .. } => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_principal_require() {
+        let member = Principal::Authenticated {
+            subject: "member".to_string(),
+            role: Role::Member,
+        };
+        let admin = Principal::Authenticated {
+            subject: "admin".to_string(),
+            role: Role::Admin,
+        };
+
+        assert!(matches!(
+            Principal::Anonymous.require(Role::Member),
+            Err(CoreError::Unauthorized(_))
+        ));
+        assert!(member.require(Role::Member).is_ok());
+        assert!(matches!(
+            member.require(Role::Admin),
+            Err(CoreError::Forbidden(_))
+        ));
+        assert!(admin.require(Role::Member).is_ok());
+        assert!(admin.require(Role::Admin).is_ok());
+    }
+}
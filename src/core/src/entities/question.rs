@@ -1,17 +1,35 @@
 use std::fmt;
-use std::io::{Error, ErrorKind};
 use std::str::FromStr;
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
+use crate::common::errors::CoreError;
+use crate::common::validation::{Validate, ValidationErrors};
 use crate::entities::entity::Entity;
+use crate::entities::tag::validate_tags;
+
+/// Longest title allowed, in characters, matching the `VARCHAR(255)` column.
+pub const MAX_TITLE_LENGTH: usize = 255;
+/// Longest content allowed, in characters.
+pub const MAX_CONTENT_LENGTH: usize = 10_000;
 
 /// Identifier for a question.
-#[derive(Debug, Serialize, Deserialize, Eq, Hash, PartialEq, Clone)]
-pub struct QuestionId(pub String);
+///
+/// Question ids are opaque UUIDs. New ids are version 7 UUIDs, so they sort in creation order.
+#[derive(Debug, Serialize, Deserialize, Eq, Hash, PartialEq, PartialOrd, Ord, Clone)]
+pub struct QuestionId(pub Uuid);
+
+impl QuestionId {
+    /// Allocates a fresh, time ordered `QuestionId`.
+    pub fn generate() -> Self {
+        QuestionId(Uuid::now_v7())
+    }
+}
 
 impl FromStr for QuestionId {
-    type Err = Error;
+    type Err = CoreError;
 
     /// Attempts to parse a string into a `QuestionId`.
     ///
@@ -21,7 +39,8 @@ impl FromStr for QuestionId {
     ///
     /// # Returns
     ///
-    /// A `Result` containing the parsed `QuestionId` or an `Error` if parsing fails.
+    /// A `Result` containing the parsed `QuestionId` or a `CoreError::InvalidParameter` if the
+    /// string is not a UUID.
     ///
     /// # Example
     ///
@@ -29,7 +48,7 @@ impl FromStr for QuestionId {
     /// use std::str::FromStr;
     /// use rust_core::entities::question::QuestionId;
     ///
-    /// let id_str = "123";
+    /// let id_str = "0190a5c4-7d0e-7c3a-9f1e-4b2d8c6a5e10";
     /// match QuestionId::from_str(id_str) {
     ///     Ok(id) => {
     ///         println!("Parsed question id: {:?}", id);
@@ -40,10 +59,9 @@ impl FromStr for QuestionId {
     /// }
     /// ```
     fn from_str(id: &str) -> Result<Self, Self::Err> {
-        match id.is_empty() {
-            false => Ok(QuestionId(id.to_string())),
-            true => Err(Error::new(ErrorKind::InvalidInput, "No id provided")),
-        }
+        Uuid::parse_str(id)
+            .map(QuestionId)
+            .map_err(|err| CoreError::InvalidParameter(format!("id: {err}")))
     }
 }
 
@@ -64,10 +82,27 @@ pub struct QuestionEntity {
     pub content: String,
     /// Optional tags associated with the question.
     pub tags: Option<Vec<String>>,
+    /// Revision of the question, starting at 1 and bumped on every update.
+    ///
+    /// An update must carry the version it was based on, so stale writes are rejected.
+    pub version: i32,
+    /// When the question was created. Maintained by the repository.
+    #[serde(default)]
+    pub created_on: DateTime<Utc>,
+    /// When the question was last changed. Maintained by the repository.
+    #[serde(default)]
+    pub updated_on: DateTime<Utc>,
+    /// When the question was soft deleted, if it was. Maintained by the repository.
+    #[serde(default)]
+    pub deleted_on: Option<DateTime<Utc>>,
 }
 
 impl QuestionEntity {
-    /// Creates a new `QuestionEntity`.
+    /// Version of a question that has never been updated.
+    pub const INITIAL_VERSION: i32 = 1;
+
+    /// Creates a new `QuestionEntity` at `QuestionEntity::INITIAL_VERSION`, created and updated
+    /// now.
     ///
     /// # Arguments
     ///
@@ -83,23 +118,142 @@ impl QuestionEntity {
     /// # Example
     ///
     /// ```rust
-    /// use std::str::FromStr;
     /// use rust_core::entities::question::{QuestionEntity, QuestionId};
     ///
-    /// let id = QuestionId::from_str("123").unwrap();
+    /// let id = QuestionId::generate();
     /// let title = "Example Title".to_string();
     /// let content = "Example Content".to_string();
     /// let tags = Some(vec!["tag1".to_string(), "tag2".to_string()]);
     /// let question = QuestionEntity::new(id, title, content, tags);
     /// ```
     pub fn new(id: QuestionId, title: String, content: String, tags: Option<Vec<String>>) -> Self {
+        let now = Utc::now();
         QuestionEntity {
             id,
             title,
             content,
             tags,
+            version: Self::INITIAL_VERSION,
+            created_on: now,
+            updated_on: now,
+            deleted_on: None,
         }
     }
 }
 
 impl Entity<QuestionEntity> for QuestionEntity {}
+
+/// Represents the fields a client supplies to create a question.
+///
+/// The identifier is left out on purpose, since the repository allocates it on insert.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct CreateQuestionEntity {
+    /// Title of the question.
+    pub title: String,
+    /// Content of the question.
+    pub content: String,
+    /// Optional tags associated with the question.
+    pub tags: Option<Vec<String>>,
+}
+
+impl CreateQuestionEntity {
+    /// Turns the payload into a `QuestionEntity` stored under the allocated `id`.
+    pub fn into_entity(self, id: QuestionId) -> QuestionEntity {
+        QuestionEntity::new(id, self.title, self.content, self.tags)
+    }
+}
+
+/// Checks the fields a client can write, collecting every problem found.
+fn validate_question(
+    title: &str,
+    content: &str,
+    tags: &Option<Vec<String>>,
+) -> Result<(), CoreError> {
+    let mut errors = ValidationErrors::default();
+    for (field, value, max_length) in [
+        ("title", title, MAX_TITLE_LENGTH),
+        ("content", content, MAX_CONTENT_LENGTH),
+    ] {
+        if value.trim().is_empty() {
+            errors.add(field, "must not be blank");
+        } else if value.chars().count() > max_length {
+            errors.add(field, format!("must be at most {max_length} characters"));
+        }
+    }
+    validate_tags(tags, &mut errors);
+    errors.into_result()
+}
+
+impl Validate for QuestionEntity {
+    fn validate(&self) -> Result<(), CoreError> {
+        validate_question(&self.title, &self.content, &self.tags)
+    }
+}
+
+impl Validate for CreateQuestionEntity {
+    fn validate(&self) -> Result<(), CoreError> {
+        validate_question(&self.title, &self.content, &self.tags)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_question_id_from_str() {
+        let id = QuestionId::from_str("0190A5C4-7D0E-7C3A-9F1E-4B2D8C6A5E10").unwrap();
+        assert_eq!(id.to_string(), "0190a5c4-7d0e-7c3a-9f1e-4b2d8c6a5e10");
+
+        for invalid in ["", "1", "not-a-uuid"] {
+            match QuestionId::from_str(invalid) {
+                Err(CoreError::InvalidParameter(_)) => {}
+                other => panic!("Expected InvalidParameter error, but got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_create_question_entity_validate() {
+        let question = CreateQuestionEntity {
+            title: "How do I pin a future?".to_string(),
+            content: "It does not compile.".to_string(),
+            tags: Some(vec!["rust".to_string(), "async".to_string()]),
+        };
+        assert!(question.validate().is_ok());
+
+        let question = CreateQuestionEntity {
+            title: " ".to_string(),
+            content: "x".repeat(MAX_CONTENT_LENGTH + 1),
+            tags: Some(vec!["rust lang".to_string()]),
+        };
+        match question.validate() {
+            Err(CoreError::Validation(errors)) => {
+                assert_eq!(errors.field("title"), ["must not be blank"]);
+                assert_eq!(
+                    errors.field("content"),
+                    [format!("must be at most {MAX_CONTENT_LENGTH} characters")]
+                );
+                assert_eq!(errors.field("tags").len(), 1);
+            }
+            other => panic!("Expected Validation error, but got {:?}", other),
+        }
+
+        // Lengths count characters, like the database does
+        let question = QuestionEntity::new(
+            QuestionId::generate(),
+            "é".repeat(MAX_TITLE_LENGTH),
+            "content".to_string(),
+            None,
+        );
+        assert!(question.validate().is_ok());
+    }
+
+    #[test]
+    fn test_question_id_generate_is_ordered() {
+        let ids = (0..100).map(|_| QuestionId::generate()).collect::<Vec<_>>();
+        let mut sorted = ids.clone();
+        sorted.sort();
+        assert_eq!(ids, sorted);
+    }
+}
@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 use std::convert::TryFrom;
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 use crate::common::errors::CoreError;
@@ -8,27 +9,105 @@ use crate::entities::filter_entity::FilterEntity;
 use crate::entities::pagination_entity::PaginationEntity;
 
 /// Represents filters for querying questions.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct QuestionFilter {
     /// Pagination parameters for the query.
     pub pagination: PaginationEntity,
+    /// Only match questions carrying every one of these tags.
+    pub tags: Option<Vec<String>>,
+    /// Only match questions whose title contains this text, ignoring case.
+    pub title: Option<String>,
+    /// Only match questions created strictly after this instant.
+    pub created_after: Option<DateTime<Utc>>,
+    /// Only match questions created strictly before this instant.
+    pub created_before: Option<DateTime<Utc>>,
+    /// Also match soft deleted questions, which are hidden by default.
+    pub include_deleted: bool,
+    /// Only match questions whose title or content contains every word of this text, ranking
+    /// them by relevance.
+    pub search: Option<String>,
+}
+
+/// Parses an optional RFC 3339 timestamp from the query parameter `key`.
+fn parse_timestamp(
+    query: &HashMap<String, String>,
+    key: &str,
+) -> Result<Option<DateTime<Utc>>, CoreError> {
+    query
+        .get(key)
+        .map(|raw| {
+            DateTime::parse_from_rfc3339(raw)
+                .map(|timestamp| timestamp.with_timezone(&Utc))
+                .map_err(|err| CoreError::InvalidParameter(format!("{key}: {err}")))
+        })
+        .transpose()
+}
+
+/// Parses an optional boolean from the query parameter `key`.
+fn parse_flag(query: &HashMap<String, String>, key: &str) -> Result<Option<bool>, CoreError> {
+    query
+        .get(key)
+        .map(|raw| {
+            raw.parse::<bool>()
+                .map_err(|err| CoreError::InvalidParameter(format!("{key}: {err}")))
+        })
+        .transpose()
 }
 
 /// Implementation of the `TryFrom` trait to convert a HashMap into a `QuestionFilter`.
 ///
 /// This implementation allows converting a HashMap containing query parameters into a `QuestionFilter`.
-/// It attempts to parse the pagination parameters from the HashMap and constructs a `QuestionFilter`
-/// instance. If parsing fails for any reason, it returns a `CoreError`.
+/// Besides the pagination parameters it understands `tags` (comma separated, lowercased like
+/// stored tags), `title`, the RFC 3339 timestamps `created_after` and `created_before`, the
+/// `include_deleted` flag and the full-text search `q`. If parsing fails for any reason, a sort key
+/// is not in `QuestionFilter::SORTABLE_FIELDS`, or `q` is combined with `sort` or `cursor`, it
+/// returns a `CoreError`.
 impl TryFrom<HashMap<String, String>> for QuestionFilter {
     type Error = CoreError;
 
     fn try_from(query: HashMap<String, String>) -> Result<Self, CoreError> {
+        let tags = query.get("tags").map(|raw| {
+            raw.split(',')
+                .map(str::trim)
+                .filter(|tag| !tag.is_empty())
+                .map(str::to_lowercase)
+                .collect::<Vec<_>>()
+        });
+        let title = query
+            .get("title")
+            .filter(|title| !title.is_empty())
+            .cloned();
+        let search = query
+            .get("q")
+            .map(|q| q.trim())
+            .filter(|q| !q.is_empty())
+            .map(str::to_string);
+        let pagination = PaginationEntity::try_from(&query)?;
+        pagination.validate_sort(Self::SORTABLE_FIELDS)?;
+        // Search results are ranked by relevance and paged by offset only.
+        if search.is_some() && (pagination.sort.is_some() || pagination.cursor.is_some()) {
+            return Err(CoreError::InvalidParameter(
+                "q: search results cannot be sorted or paged by cursor".to_string(),
+            ));
+        }
+
         Ok(QuestionFilter {
-            pagination: PaginationEntity::try_from(query)?,
+            pagination,
+            tags: tags.filter(|tags| !tags.is_empty()),
+            title,
+            created_after: parse_timestamp(&query, "created_after")?,
+            created_before: parse_timestamp(&query, "created_before")?,
+            include_deleted: parse_flag(&query, "include_deleted")?.unwrap_or_default(),
+            search,
         })
     }
 }
 
+impl QuestionFilter {
+    /// Fields questions can be sorted by through `PaginationEntity::sort`.
+    pub const SORTABLE_FIELDS: &'static [&'static str] = &["id", "title", "created_on"];
+}
+
 impl FilterEntity<QuestionFilter> for QuestionFilter {}
 
 #[cfg(test)]
@@ -46,6 +125,10 @@ mod tests {
             Ok(question_filter) => {
                 assert_eq!(question_filter.pagination.start, 0);
                 assert_eq!(question_filter.pagination.end, 10);
+                assert_eq!(question_filter.tags, None);
+                assert_eq!(question_filter.title, None);
+                assert!(!question_filter.include_deleted);
+                assert_eq!(question_filter.search, None);
             }
             Err(err) => {
                 panic!("Unexpected error: {:?}", err);
@@ -72,4 +155,85 @@ mod tests {
             },
         }
     }
+
+    #[test]
+    fn test_question_filter_from_query_criteria() {
+        // Test case 3: Filtering criteria
+        let mut query_params_3 = HashMap::new();
+        query_params_3.insert("tags".to_string(), "rust, Warp,,".to_string());
+        query_params_3.insert("title".to_string(), "Tests".to_string());
+        query_params_3.insert(
+            "created_after".to_string(),
+            "2024-01-01T00:00:00Z".to_string(),
+        );
+        query_params_3.insert(
+            "created_before".to_string(),
+            "2024-01-02T00:00:00+07:00".to_string(),
+        );
+        query_params_3.insert("include_deleted".to_string(), "true".to_string());
+        query_params_3.insert("q".to_string(), " borrow checker ".to_string());
+
+        let question_filter = QuestionFilter::try_from(query_params_3).unwrap();
+        assert_eq!(
+            question_filter.tags,
+            Some(vec!["rust".to_string(), "warp".to_string()])
+        );
+        assert_eq!(question_filter.title, Some("Tests".to_string()));
+        assert_eq!(
+            question_filter.created_after.unwrap().to_rfc3339(),
+            "2024-01-01T00:00:00+00:00"
+        );
+        assert_eq!(
+            question_filter.created_before.unwrap().to_rfc3339(),
+            "2024-01-01T17:00:00+00:00"
+        );
+        assert!(question_filter.include_deleted);
+        assert_eq!(question_filter.search, Some("borrow checker".to_string()));
+    }
+
+    #[test]
+    fn test_question_filter_from_query_unknown_sort_field() {
+        let mut query_params = HashMap::new();
+        query_params.insert("sort".to_string(), "-content".to_string());
+
+        match QuestionFilter::try_from(query_params) {
+            Err(CoreError::InvalidParameter(_)) => {}
+            other => panic!("Expected InvalidParameter error, but got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_question_filter_from_query_sorted_search() {
+        let mut query_params = HashMap::new();
+        query_params.insert("q".to_string(), "lifetimes".to_string());
+        query_params.insert("sort".to_string(), "title".to_string());
+
+        match QuestionFilter::try_from(query_params) {
+            Err(CoreError::InvalidParameter(_)) => {}
+            other => panic!("Expected InvalidParameter error, but got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_question_filter_from_query_invalid_timestamp() {
+        // Test case 4: Malformed timestamp
+        let mut query_params_4 = HashMap::new();
+        query_params_4.insert("created_after".to_string(), "yesterday".to_string());
+
+        match QuestionFilter::try_from(query_params_4) {
+            Err(CoreError::InvalidParameter(_)) => {}
+            other => panic!("Expected InvalidParameter error, but got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_question_filter_from_query_invalid_flag() {
+        let mut query_params = HashMap::new();
+        query_params.insert("include_deleted".to_string(), "yes".to_string());
+
+        match QuestionFilter::try_from(query_params) {
+            Err(CoreError::InvalidParameter(_)) => {}
+            other => panic!("Expected InvalidParameter error, but got {:?}", other),
+        }
+    }
 }
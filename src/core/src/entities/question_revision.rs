@@ -0,0 +1,136 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::entities::entity::Entity;
+use crate::entities::question::{QuestionEntity, QuestionId};
+
+/// Snapshot of a question's editable fields, taken whenever they are written.
+///
+/// The revision number is the question version the snapshot was taken at. Versions bumped without
+/// touching the fields, such as a soft delete, leave no revision behind.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct QuestionRevisionEntity {
+    /// Identifier of the question the revision belongs to.
+    pub question_id: QuestionId,
+    /// Question version captured by the revision.
+    pub revision: i32,
+    /// Title of the question at that revision.
+    pub title: String,
+    /// Content of the question at that revision.
+    pub content: String,
+    /// Tags of the question at that revision.
+    pub tags: Option<Vec<String>>,
+    /// When the revision was written.
+    pub created_on: DateTime<Utc>,
+}
+
+/// A field that differs between two revisions.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct RevisionChangeEntity {
+    /// Name of the changed field.
+    pub field: String,
+    /// Value before the change, absent for the first revision.
+    pub from: Option<Value>,
+    /// Value after the change.
+    pub to: Value,
+}
+
+impl QuestionRevisionEntity {
+    /// Captures the editable fields of `question` at its current version.
+    pub fn of(question: &QuestionEntity) -> Self {
+        QuestionRevisionEntity {
+            question_id: question.id.clone(),
+            revision: question.version,
+            title: question.title.clone(),
+            content: question.content.clone(),
+            tags: question.tags.clone(),
+            created_on: question.updated_on,
+        }
+    }
+
+    /// Lists the fields this revision changed compared to `previous`.
+    ///
+    /// Every field counts as changed for the first revision, which has no `previous`.
+    pub fn diff(&self, previous: Option<&QuestionRevisionEntity>) -> Vec<RevisionChangeEntity> {
+        let fields = |revision: &QuestionRevisionEntity| {
+            [
+                ("title", Value::from(revision.title.clone())),
+                ("content", Value::from(revision.content.clone())),
+                ("tags", Value::from(revision.tags.clone())),
+            ]
+        };
+        let before = previous.map(fields);
+
+        fields(self)
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, (field, to))| {
+                let from = before.as_ref().map(|before| before[index].1.clone());
+                (from.as_ref() != Some(&to)).then(|| RevisionChangeEntity {
+                    field: field.to_string(),
+                    from,
+                    to,
+                })
+            })
+            .collect()
+    }
+}
+
+impl Entity<QuestionRevisionEntity> for QuestionRevisionEntity {}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use serde_json::json;
+
+    use super::*;
+
+    fn revision(revision: i32, title: &str, tags: Option<Vec<&str>>) -> QuestionRevisionEntity {
+        QuestionRevisionEntity {
+            question_id: QuestionId::from_str("0190a5c4-7d0e-7c3a-9f1e-4b2d8c6a5e10").unwrap(),
+            revision,
+            title: title.to_string(),
+            content: "content".to_string(),
+            tags: tags.map(|tags| tags.into_iter().map(str::to_string).collect()),
+            created_on: DateTime::<Utc>::default(),
+        }
+    }
+
+    #[test]
+    fn test_revision_diff() {
+        let first = revision(1, "Borrow checker", None);
+        assert_eq!(
+            first.diff(None),
+            vec![
+                RevisionChangeEntity {
+                    field: "title".to_string(),
+                    from: None,
+                    to: json!("Borrow checker"),
+                },
+                RevisionChangeEntity {
+                    field: "content".to_string(),
+                    from: None,
+                    to: json!("content"),
+                },
+                RevisionChangeEntity {
+                    field: "tags".to_string(),
+                    from: None,
+                    to: json!(null),
+                },
+            ]
+        );
+
+        let second = revision(2, "Borrow checker", Some(vec!["rust"]));
+        assert_eq!(
+            second.diff(Some(&first)),
+            vec![RevisionChangeEntity {
+                field: "tags".to_string(),
+                from: Some(json!(null)),
+                to: json!(["rust"]),
+            }]
+        );
+        assert!(second.diff(Some(&second)).is_empty());
+    }
+}
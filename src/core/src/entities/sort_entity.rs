@@ -0,0 +1,101 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::common::errors::CoreError;
+
+/// Direction in which a sort key is applied.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+/// A single sort key, such as `-created_on` for newest first.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SortEntity {
+    /// Name of the field to sort by.
+    pub field: String,
+    /// Direction of the sort.
+    pub direction: SortDirection,
+}
+
+impl SortEntity {
+    /// Parses a comma separated list of sort keys, e.g. `-created_on,title`.
+    ///
+    /// A leading `-` sorts the field in descending order, a leading `+` or no prefix in ascending
+    /// order. Empty keys are skipped, while a key without a field name or a field listed twice is
+    /// rejected with `CoreError::InvalidParameter`.
+    pub fn parse_list(raw: &str) -> Result<Vec<SortEntity>, CoreError> {
+        let mut sort: Vec<SortEntity> = Vec::new();
+        for key in raw.split(',').map(str::trim).filter(|key| !key.is_empty()) {
+            let entity = SortEntity::from_str(key)?;
+            if sort.iter().any(|existing| existing.field == entity.field) {
+                return Err(CoreError::InvalidParameter(format!(
+                    "sort: field `{}` is listed more than once",
+                    entity.field
+                )));
+            }
+            sort.push(entity);
+        }
+        Ok(sort)
+    }
+}
+
+impl FromStr for SortEntity {
+    type Err = CoreError;
+
+    fn from_str(key: &str) -> Result<Self, Self::Err> {
+        let (direction, field) = match key.strip_prefix('-') {
+            Some(field) => (SortDirection::Desc, field),
+            None => (SortDirection::Asc, key.strip_prefix('+').unwrap_or(key)),
+        };
+        let field = field.trim();
+        if field.is_empty() {
+            return Err(CoreError::InvalidParameter(format!(
+                "sort: `{key}` has no field name"
+            )));
+        }
+        Ok(SortEntity {
+            field: field.to_string(),
+            direction,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sort_entity_parse_list() {
+        let sort = SortEntity::parse_list("-created_on, title,,+id").unwrap();
+        assert_eq!(
+            sort,
+            vec![
+                SortEntity {
+                    field: "created_on".to_string(),
+                    direction: SortDirection::Desc,
+                },
+                SortEntity {
+                    field: "title".to_string(),
+                    direction: SortDirection::Asc,
+                },
+                SortEntity {
+                    field: "id".to_string(),
+                    direction: SortDirection::Asc,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sort_entity_parse_list_invalid() {
+        for raw in ["-", "title,-title"] {
+            match SortEntity::parse_list(raw) {
+                Err(CoreError::InvalidParameter(_)) => {}
+                other => panic!("Expected InvalidParameter error, but got {:?}", other),
+            }
+        }
+    }
+}
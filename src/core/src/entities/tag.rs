@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+
+use crate::common::validation::ValidationErrors;
+use crate::entities::entity::Entity;
+
+/// Most tags a question may carry.
+pub const MAX_TAGS: usize = 5;
+/// Longest tag name allowed, in characters.
+pub const MAX_TAG_LENGTH: usize = 35;
+
+/// Represents a tag along with how many questions carry it.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TagEntity {
+    /// Normalized name of the tag.
+    pub name: String,
+    /// Number of questions carrying the tag, soft deleted ones left out.
+    pub count: usize,
+}
+
+impl Entity<TagEntity> for TagEntity {}
+
+/// Normalizes tags before they are written.
+///
+/// Names are trimmed and lowercased, blank ones are dropped, and duplicates are removed keeping
+/// the first occurrence, so `["Rust", " rust", "Warp"]` becomes `["rust", "warp"]`.
+pub fn normalize_tags(tags: Option<Vec<String>>) -> Option<Vec<String>> {
+    tags.map(|tags| {
+        let mut normalized: Vec<String> = Vec::with_capacity(tags.len());
+        for tag in tags {
+            let tag = tag.trim().to_lowercase();
+            if !tag.is_empty() && !normalized.contains(&tag) {
+                normalized.push(tag);
+            }
+        }
+        normalized
+    })
+}
+
+/// Checks tags as they will be stored, recording problems under the `tags` field.
+///
+/// Tags are normalized first, so case, duplicates and blanks do not count against `MAX_TAGS`.
+/// Names may only use letters, digits and `+ # . -`, which covers names like `c++` or `.net`.
+pub fn validate_tags(tags: &Option<Vec<String>>, errors: &mut ValidationErrors) {
+    let Some(tags) = normalize_tags(tags.clone()) else {
+        return;
+    };
+    if tags.len() > MAX_TAGS {
+        errors.add("tags", format!("at most {MAX_TAGS} tags are allowed"));
+    }
+    for tag in &tags {
+        if tag.chars().count() > MAX_TAG_LENGTH {
+            errors.add(
+                "tags",
+                format!("`{tag}` must be at most {MAX_TAG_LENGTH} characters"),
+            );
+        }
+        if !tag
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '+' | '#' | '.' | '-'))
+        {
+            errors.add(
+                "tags",
+                format!("`{tag}` may only contain letters, digits and + # . -"),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_tags() {
+        let tags = vec!["Rust", " rust", "Warp", "", "RUST", "warp "]
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        assert_eq!(
+            normalize_tags(Some(tags)),
+            Some(vec!["rust".to_string(), "warp".to_string()])
+        );
+        assert_eq!(normalize_tags(Some(vec![])), Some(vec![]));
+        assert_eq!(normalize_tags(None), None);
+    }
+
+    #[test]
+    fn test_validate_tags() {
+        let validate = |tags: Option<Vec<&str>>| {
+            let mut errors = ValidationErrors::default();
+            validate_tags(
+                &tags.map(|tags| tags.into_iter().map(str::to_string).collect()),
+                &mut errors,
+            );
+            errors.field("tags").len()
+        };
+
+        assert_eq!(validate(None), 0);
+        assert_eq!(validate(Some(vec!["c++", "C#", ".net", "tokio-rs"])), 0);
+        // Duplicates collapse before counting
+        assert_eq!(validate(Some(vec!["a", "b", "c", "d", "e", "A", " e "])), 0);
+        assert_eq!(validate(Some(vec!["a", "b", "c", "d", "e", "f"])), 1);
+        assert_eq!(validate(Some(vec!["rust lang", "warp/hyper"])), 2);
+        assert_eq!(validate(Some(vec![&"x".repeat(MAX_TAG_LENGTH + 1)])), 1);
+    }
+}
@@ -1,3 +1,4 @@
 pub mod common;
 pub mod entities;
 pub mod ports;
+pub mod services;
@@ -0,0 +1,19 @@
+use async_trait::async_trait;
+
+use crate::common::errors::CoreError;
+use crate::entities::answer::{AnswerEntity, AnswerId, CreateAnswerEntity};
+use crate::entities::question::QuestionId;
+
+#[async_trait]
+pub trait AnswerPort {
+    /// Stores an answer to the question, failing with `CoreError::NotFound` when the question
+    /// does not exist.
+    async fn add(
+        &self,
+        question_id: &QuestionId,
+        answer: CreateAnswerEntity,
+    ) -> Result<AnswerEntity, CoreError>;
+    async fn get(&self, answer_id: &AnswerId) -> Result<AnswerEntity, CoreError>;
+    /// Lists the answers to a question, oldest first.
+    async fn list(&self, question_id: &QuestionId) -> Result<Vec<AnswerEntity>, CoreError>;
+}
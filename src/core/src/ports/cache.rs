@@ -45,5 +45,107 @@ pub trait CachePort {
     /// # Returns
     ///
     /// Returns `Result(())` if the key-value pair is successfully removed from the cache, `false` otherwise.
-    async fn del(&mut self, key: &str) -> Result<(), CoreError>;
+    async fn del(&self, key: &str) -> Result<(), CoreError>;
+
+    /// Retrieves the values of several keys at once.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys`: The keys to look up in the cache.
+    ///
+    /// # Returns
+    ///
+    /// Returns the value of every key in the order of `keys`, `None` for the keys not present in the cache.
+    async fn mget(&self, keys: &[&str]) -> Result<Vec<Option<String>>, CoreError>;
+
+    /// Sets several key-value pairs at once, all with the same optional expiration duration.
+    ///
+    /// # Arguments
+    ///
+    /// * `entries`: The key-value pairs to set in the cache.
+    /// * `expiration`: Optional expiration duration for every key-value pair.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Result(())` if every key-value pair is set, none of them being set otherwise.
+    async fn mset(
+        &self,
+        entries: &[(&str, &str)],
+        expiration: Option<Duration>,
+    ) -> Result<(), CoreError>;
+
+    /// Checks whether a key is present in the cache.
+    ///
+    /// # Arguments
+    ///
+    /// * `key`: The key to look up in the cache.
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if the key is present and not expired, `false` otherwise.
+    async fn exists(&self, key: &str) -> Result<bool, CoreError>;
+
+    /// Retrieves how long a key has left before it expires.
+    ///
+    /// # Arguments
+    ///
+    /// * `key`: The key to look up in the cache.
+    ///
+    /// # Returns
+    ///
+    /// Returns the remaining time to live, `None` if the key never expires, or `CoreError::NotFound` if the key is not present in the cache.
+    async fn ttl(&self, key: &str) -> Result<Option<Duration>, CoreError>;
+
+    /// Sets the expiration duration of a key already in the cache.
+    ///
+    /// # Arguments
+    ///
+    /// * `key`: The key to expire.
+    /// * `expiration`: Expiration duration, counted from now.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Result(())` if the expiration is set, or `CoreError::NotFound` if the key is not present in the cache.
+    async fn expire(&self, key: &str, expiration: Duration) -> Result<(), CoreError>;
+
+    /// Atomically adds to the integer stored under a key, starting from zero when the key is not present.
+    ///
+    /// # Arguments
+    ///
+    /// * `key`: The key of the counter.
+    /// * `delta`: The amount to add, negative to subtract.
+    ///
+    /// # Returns
+    ///
+    /// Returns the value after the addition, or `CoreError::InvalidParameter` if the stored value is not an integer.
+    async fn incr_by(&self, key: &str, delta: i64) -> Result<i64, CoreError>;
+
+    /// Atomically sets a key-value pair only if the key is not present in the cache.
+    ///
+    /// # Arguments
+    ///
+    /// * `key`: The key to set in the cache.
+    /// * `value`: The value to associate with the key.
+    /// * `expiration`: Optional expiration duration for the key-value pair.
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if the key-value pair is set, `false` if the key was already present.
+    async fn set_if_absent(
+        &self,
+        key: &str,
+        value: &str,
+        expiration: Option<Duration>,
+    ) -> Result<bool, CoreError>;
+
+    /// Removes every key starting with a prefix.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix`: The prefix of the keys to remove, taken literally.
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of keys removed.
+    async fn delete_by_pattern(&self, prefix: &str) -> Result<usize, CoreError>;
 }
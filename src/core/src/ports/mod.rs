@@ -1,3 +1,6 @@
+pub mod answer;
 pub mod cache;
 pub mod gpt_answer;
 pub mod question;
+pub mod question_revision;
+pub mod tag;
@@ -1,17 +1,35 @@
 use async_trait::async_trait;
 
 use crate::common::errors::CoreError;
-use crate::entities::question::{QuestionEntity, QuestionId};
+use crate::entities::page_entity::PageEntity;
+use crate::entities::question::{CreateQuestionEntity, QuestionEntity, QuestionId};
 use crate::entities::question_filter::QuestionFilter;
 
 #[async_trait]
 pub trait QuestionPort {
-    async fn add(&self, question: QuestionEntity) -> Result<QuestionEntity, CoreError>;
+    async fn add(&self, question: CreateQuestionEntity) -> Result<QuestionEntity, CoreError>;
+    /// Stores `question` as the next version, failing with `CoreError::Conflict` when
+    /// `question.version` is no longer the stored one.
     async fn update(&self, question: QuestionEntity) -> Result<QuestionEntity, CoreError>;
-    async fn delete(&self, question_id: &QuestionId) -> Result<(), CoreError>;
+    /// Soft deletes the question, failing with `CoreError::Conflict` when `version` is given and
+    /// is no longer the stored one. Deleted questions are hidden until restored.
+    async fn delete(&self, question_id: &QuestionId, version: Option<i32>)
+        -> Result<(), CoreError>;
+    /// Brings back a soft deleted question. Restoring a question that is not deleted is a no-op.
+    async fn restore(&self, question_id: &QuestionId) -> Result<QuestionEntity, CoreError>;
+    /// Permanently removes a soft deleted question, failing with `CoreError::Conflict` when the
+    /// question has not been deleted first.
+    async fn purge(&self, question_id: &QuestionId) -> Result<(), CoreError>;
     async fn get(&self, question_id: &QuestionId) -> Result<QuestionEntity, CoreError>;
     async fn list(
         &self,
         question_filter: &QuestionFilter,
-    ) -> Result<Vec<QuestionEntity>, CoreError>;
+    ) -> Result<PageEntity<QuestionEntity>, CoreError>;
+    /// Lists the questions matching `question_filter.search` along with the other criteria, most
+    /// relevant first, paged by offset. Without a search term it behaves like `list`.
+    async fn search(
+        &self,
+        question_filter: &QuestionFilter,
+    ) -> Result<PageEntity<QuestionEntity>, CoreError>;
+    async fn count(&self, question_filter: &QuestionFilter) -> Result<usize, CoreError>;
 }
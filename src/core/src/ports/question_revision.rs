@@ -0,0 +1,20 @@
+use async_trait::async_trait;
+
+use crate::common::errors::CoreError;
+use crate::entities::question::QuestionId;
+use crate::entities::question_revision::QuestionRevisionEntity;
+
+/// Read access to the revisions recorded whenever a question is added or updated.
+#[async_trait]
+pub trait QuestionRevisionPort {
+    /// Lists the revisions of a question, oldest first.
+    async fn list(
+        &self,
+        question_id: &QuestionId,
+    ) -> Result<Vec<QuestionRevisionEntity>, CoreError>;
+    async fn get(
+        &self,
+        question_id: &QuestionId,
+        revision: i32,
+    ) -> Result<QuestionRevisionEntity, CoreError>;
+}
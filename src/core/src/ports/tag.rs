@@ -0,0 +1,12 @@
+use async_trait::async_trait;
+
+use crate::common::errors::CoreError;
+use crate::entities::tag::TagEntity;
+
+/// Read access to the tags carried by questions.
+#[async_trait]
+pub trait TagPort {
+    /// Lists the tags carried by at least one question that is not soft deleted, most used
+    /// first and then by name.
+    async fn list(&self) -> Result<Vec<TagEntity>, CoreError>;
+}
@@ -0,0 +1,162 @@
+use std::sync::Arc;
+
+use crate::common::errors::CoreError;
+use crate::common::validation::Validate;
+use crate::entities::answer::{AnswerEntity, AnswerId, CreateAnswerEntity};
+use crate::entities::question::QuestionId;
+use crate::ports::answer::AnswerPort;
+use crate::ports::gpt_answer::GptAnswerPort;
+use crate::ports::question::QuestionPort;
+
+/// Source recorded on answers produced through the `GptAnswerPort`.
+pub const GPT_ANSWER_SOURCE: &str = "gpt_answer_server";
+
+/// Use cases around the answers to a question, written by people or generated.
+#[derive(Clone)]
+pub struct AnswerService {
+    question_port: Arc<dyn QuestionPort + Send + Sync>,
+    answer_port: Arc<dyn AnswerPort + Send + Sync>,
+    gpt_answer_port: Arc<dyn GptAnswerPort + Send + Sync>,
+}
+
+impl AnswerService {
+    pub fn new(
+        question_port: Arc<dyn QuestionPort + Send + Sync>,
+        answer_port: Arc<dyn AnswerPort + Send + Sync>,
+        gpt_answer_port: Arc<dyn GptAnswerPort + Send + Sync>,
+    ) -> Self {
+        AnswerService {
+            question_port,
+            answer_port,
+            gpt_answer_port,
+        }
+    }
+
+    /// Lists the answers to a question, oldest first. Deleted questions have no visible
+    /// answers.
+    pub async fn list(&self, question_id: &QuestionId) -> Result<Vec<AnswerEntity>, CoreError> {
+        self.question_port.get(question_id).await?;
+        self.answer_port.list(question_id).await
+    }
+
+    /// Gets an answer, failing with `CoreError::NotFound` when it does not belong to the
+    /// question.
+    pub async fn get(
+        &self,
+        question_id: &QuestionId,
+        answer_id: &AnswerId,
+    ) -> Result<AnswerEntity, CoreError> {
+        self.question_port.get(question_id).await?;
+        let answer = self.answer_port.get(answer_id).await?;
+        if &answer.question_id != question_id {
+            return Err(CoreError::NotFound);
+        }
+        Ok(answer)
+    }
+
+    /// Stores an answer written by a person.
+    pub async fn add(
+        &self,
+        question_id: &QuestionId,
+        body: String,
+    ) -> Result<AnswerEntity, CoreError> {
+        let answer = CreateAnswerEntity {
+            body,
+            source_model: None,
+        };
+        answer.validate()?;
+        self.answer_port.add(question_id, answer).await
+    }
+
+    /// Asks the `GptAnswerPort` to answer the question and stores the answer under
+    /// `GPT_ANSWER_SOURCE`.
+    pub async fn generate(&self, question_id: &QuestionId) -> Result<AnswerEntity, CoreError> {
+        let question = self.question_port.get(question_id).await?;
+        let body = self.gpt_answer_port.get_answer(&question.content).await?;
+        let answer = CreateAnswerEntity {
+            body,
+            source_model: Some(GPT_ANSWER_SOURCE.to_string()),
+        };
+        self.answer_port.add(question_id, answer).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::question::QuestionEntity;
+    use crate::services::fakes::{FakeAnswerPort, FakeGptAnswerPort, FakeQuestionPort};
+
+    fn service(questions: Vec<QuestionEntity>) -> AnswerService {
+        AnswerService::new(
+            Arc::new(FakeQuestionPort::with(questions)),
+            Arc::new(FakeAnswerPort::default()),
+            Arc::new(FakeGptAnswerPort("Use fake ports".to_string())),
+        )
+    }
+
+    fn question() -> QuestionEntity {
+        QuestionEntity::new(
+            QuestionId::generate(),
+            "How to test services?".to_string(),
+            "With what?".to_string(),
+            None,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_add_validates_the_body() {
+        let question = question();
+        let answer_service = service(vec![question.clone()]);
+
+        assert!(matches!(
+            answer_service.add(&question.id, "  ".to_string()).await,
+            Err(CoreError::Validation(_))
+        ));
+
+        let answer = answer_service
+            .add(&question.id, "By hand".to_string())
+            .await
+            .unwrap();
+        assert_eq!(answer.source_model, None);
+        assert_eq!(
+            answer_service.list(&question.id).await.unwrap(),
+            vec![answer]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_only_finds_answers_to_the_question() {
+        let question = question();
+        let other = self::question();
+        let answer_service = service(vec![question.clone(), other.clone()]);
+
+        let answer = answer_service
+            .add(&question.id, "By hand".to_string())
+            .await
+            .unwrap();
+        assert_eq!(
+            answer_service.get(&question.id, &answer.id).await.unwrap(),
+            answer
+        );
+        assert!(matches!(
+            answer_service.get(&other.id, &answer.id).await,
+            Err(CoreError::NotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_generate_records_the_source() {
+        let question = question();
+        let answer_service = service(vec![question.clone()]);
+
+        let answer = answer_service.generate(&question.id).await.unwrap();
+        assert_eq!(answer.body, "Use fake ports");
+        assert_eq!(answer.source_model.as_deref(), Some(GPT_ANSWER_SOURCE));
+
+        assert!(matches!(
+            answer_service.generate(&QuestionId::generate()).await,
+            Err(CoreError::NotFound)
+        ));
+    }
+}
@@ -0,0 +1,244 @@
+//! Ports backed by plain collections, for testing the services on their own.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::common::errors::CoreError;
+use crate::entities::answer::{AnswerEntity, AnswerId, CreateAnswerEntity};
+use crate::entities::page_entity::PageEntity;
+use crate::entities::question::{CreateQuestionEntity, QuestionEntity, QuestionId};
+use crate::entities::question_filter::QuestionFilter;
+use crate::entities::question_revision::QuestionRevisionEntity;
+use crate::entities::tag::TagEntity;
+use crate::ports::answer::AnswerPort;
+use crate::ports::gpt_answer::GptAnswerPort;
+use crate::ports::question::QuestionPort;
+use crate::ports::question_revision::QuestionRevisionPort;
+use crate::ports::tag::TagPort;
+
+/// Keeps questions in a map and records the name of every method called on it.
+#[derive(Default)]
+pub struct FakeQuestionPort {
+    pub questions: Mutex<HashMap<QuestionId, QuestionEntity>>,
+    pub calls: Mutex<Vec<&'static str>>,
+}
+
+impl FakeQuestionPort {
+    pub fn with(questions: Vec<QuestionEntity>) -> Self {
+        FakeQuestionPort {
+            questions: Mutex::new(
+                questions
+                    .into_iter()
+                    .map(|question| (question.id.clone(), question))
+                    .collect(),
+            ),
+            ..Default::default()
+        }
+    }
+
+    fn call(&self, name: &'static str) {
+        self.calls.lock().unwrap().push(name);
+    }
+
+    fn live(&self) -> Vec<QuestionEntity> {
+        self.questions
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|question| question.deleted_on.is_none())
+            .cloned()
+            .collect()
+    }
+}
+
+#[async_trait]
+impl QuestionPort for FakeQuestionPort {
+    async fn add(&self, question: CreateQuestionEntity) -> Result<QuestionEntity, CoreError> {
+        self.call("add");
+        let question = question.into_entity(QuestionId::generate());
+        self.questions
+            .lock()
+            .unwrap()
+            .insert(question.id.clone(), question.clone());
+        Ok(question)
+    }
+
+    async fn update(&self, question: QuestionEntity) -> Result<QuestionEntity, CoreError> {
+        self.call("update");
+        let mut questions = self.questions.lock().unwrap();
+        let stored = questions.get_mut(&question.id).ok_or(CoreError::NotFound)?;
+        if stored.version != question.version {
+            return Err(CoreError::Conflict(format!(
+                "question is at version {}",
+                stored.version
+            )));
+        }
+        *stored = QuestionEntity {
+            version: question.version + 1,
+            ..question
+        };
+        Ok(stored.clone())
+    }
+
+    async fn delete(
+        &self,
+        question_id: &QuestionId,
+        _version: Option<i32>,
+    ) -> Result<(), CoreError> {
+        self.call("delete");
+        let mut questions = self.questions.lock().unwrap();
+        let stored = questions.get_mut(question_id).ok_or(CoreError::NotFound)?;
+        stored.deleted_on = Some(Utc::now());
+        Ok(())
+    }
+
+    async fn restore(&self, question_id: &QuestionId) -> Result<QuestionEntity, CoreError> {
+        self.call("restore");
+        let mut questions = self.questions.lock().unwrap();
+        let stored = questions.get_mut(question_id).ok_or(CoreError::NotFound)?;
+        stored.deleted_on = None;
+        Ok(stored.clone())
+    }
+
+    async fn purge(&self, question_id: &QuestionId) -> Result<(), CoreError> {
+        self.call("purge");
+        self.questions
+            .lock()
+            .unwrap()
+            .remove(question_id)
+            .map(|_| ())
+            .ok_or(CoreError::NotFound)
+    }
+
+    async fn get(&self, question_id: &QuestionId) -> Result<QuestionEntity, CoreError> {
+        self.call("get");
+        self.questions
+            .lock()
+            .unwrap()
+            .get(question_id)
+            .filter(|question| question.deleted_on.is_none())
+            .cloned()
+            .ok_or(CoreError::NotFound)
+    }
+
+    async fn list(
+        &self,
+        _question_filter: &QuestionFilter,
+    ) -> Result<PageEntity<QuestionEntity>, CoreError> {
+        self.call("list");
+        Ok(PageEntity {
+            items: self.live(),
+            next_cursor: None,
+        })
+    }
+
+    async fn search(
+        &self,
+        _question_filter: &QuestionFilter,
+    ) -> Result<PageEntity<QuestionEntity>, CoreError> {
+        self.call("search");
+        Ok(PageEntity {
+            items: self.live(),
+            next_cursor: None,
+        })
+    }
+
+    async fn count(&self, _question_filter: &QuestionFilter) -> Result<usize, CoreError> {
+        self.call("count");
+        Ok(self.live().len())
+    }
+}
+
+/// Serves a fixed history, oldest revision first.
+#[derive(Default)]
+pub struct FakeQuestionRevisionPort(pub Vec<QuestionRevisionEntity>);
+
+#[async_trait]
+impl QuestionRevisionPort for FakeQuestionRevisionPort {
+    async fn list(
+        &self,
+        question_id: &QuestionId,
+    ) -> Result<Vec<QuestionRevisionEntity>, CoreError> {
+        Ok(self
+            .0
+            .iter()
+            .filter(|revision| &revision.question_id == question_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn get(
+        &self,
+        question_id: &QuestionId,
+        revision: i32,
+    ) -> Result<QuestionRevisionEntity, CoreError> {
+        self.0
+            .iter()
+            .find(|candidate| {
+                &candidate.question_id == question_id && candidate.revision == revision
+            })
+            .cloned()
+            .ok_or(CoreError::NotFound)
+    }
+}
+
+/// Keeps answers in a list.
+#[derive(Default)]
+pub struct FakeAnswerPort(pub Mutex<Vec<AnswerEntity>>);
+
+#[async_trait]
+impl AnswerPort for FakeAnswerPort {
+    async fn add(
+        &self,
+        question_id: &QuestionId,
+        answer: CreateAnswerEntity,
+    ) -> Result<AnswerEntity, CoreError> {
+        let answer = answer.into_entity(AnswerId::generate(), question_id.clone());
+        self.0.lock().unwrap().push(answer.clone());
+        Ok(answer)
+    }
+
+    async fn get(&self, answer_id: &AnswerId) -> Result<AnswerEntity, CoreError> {
+        self.0
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|answer| &answer.id == answer_id)
+            .cloned()
+            .ok_or(CoreError::NotFound)
+    }
+
+    async fn list(&self, question_id: &QuestionId) -> Result<Vec<AnswerEntity>, CoreError> {
+        Ok(self
+            .0
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|answer| &answer.question_id == question_id)
+            .cloned()
+            .collect())
+    }
+}
+
+/// Answers every question with the same text.
+pub struct FakeGptAnswerPort(pub String);
+
+#[async_trait]
+impl GptAnswerPort for FakeGptAnswerPort {
+    async fn get_answer(&self, _question: &str) -> Result<String, CoreError> {
+        Ok(self.0.clone())
+    }
+}
+
+/// Serves a fixed list of tags.
+pub struct FakeTagPort(pub Vec<TagEntity>);
+
+#[async_trait]
+impl TagPort for FakeTagPort {
+    async fn list(&self) -> Result<Vec<TagEntity>, CoreError> {
+        Ok(self.0.clone())
+    }
+}
@@ -0,0 +1,6 @@
+pub mod answer;
+pub mod question;
+pub mod tag;
+
+#[cfg(test)]
+mod fakes;
@@ -0,0 +1,335 @@
+use std::sync::Arc;
+
+use crate::common::errors::CoreError;
+use crate::common::validation::Validate;
+use crate::entities::page_entity::PageEntity;
+use crate::entities::principal::{Principal, Role};
+use crate::entities::question::{CreateQuestionEntity, QuestionEntity, QuestionId};
+use crate::entities::question_filter::QuestionFilter;
+use crate::entities::question_revision::{QuestionRevisionEntity, RevisionChangeEntity};
+use crate::ports::question::QuestionPort;
+use crate::ports::question_revision::QuestionRevisionPort;
+
+/// Use cases around questions and their history.
+///
+/// Front ends call the service rather than the ports, so input is validated, deleted questions
+/// stay hidden and admin operations are authorized the same way whichever way a request comes in.
+#[derive(Clone)]
+pub struct QuestionService {
+    question_port: Arc<dyn QuestionPort + Send + Sync>,
+    question_revision_port: Arc<dyn QuestionRevisionPort + Send + Sync>,
+}
+
+impl QuestionService {
+    pub fn new(
+        question_port: Arc<dyn QuestionPort + Send + Sync>,
+        question_revision_port: Arc<dyn QuestionRevisionPort + Send + Sync>,
+    ) -> Self {
+        QuestionService {
+            question_port,
+            question_revision_port,
+        }
+    }
+
+    /// Lists a page of the questions matching the filter, along with the number of matches
+    /// across all pages. With a search term the page is ranked by relevance.
+    pub async fn list(
+        &self,
+        question_filter: &QuestionFilter,
+    ) -> Result<(PageEntity<QuestionEntity>, usize), CoreError> {
+        let page = match question_filter.search {
+            Some(_) => self.question_port.search(question_filter).await?,
+            None => self.question_port.list(question_filter).await?,
+        };
+        let total = self.question_port.count(question_filter).await?;
+        Ok((page, total))
+    }
+
+    pub async fn get(&self, question_id: &QuestionId) -> Result<QuestionEntity, CoreError> {
+        self.question_port.get(question_id).await
+    }
+
+    /// Validates and stores a new question.
+    pub async fn add(&self, question: CreateQuestionEntity) -> Result<QuestionEntity, CoreError> {
+        question.validate()?;
+        self.question_port.add(question).await
+    }
+
+    /// Validates and stores `question` as the next version, failing with `CoreError::Conflict`
+    /// when `question.version` is no longer the stored one.
+    pub async fn update(&self, question: QuestionEntity) -> Result<QuestionEntity, CoreError> {
+        question.validate()?;
+        self.question_port.update(question).await
+    }
+
+    pub async fn delete(
+        &self,
+        question_id: &QuestionId,
+        version: Option<i32>,
+    ) -> Result<(), CoreError> {
+        self.question_port.delete(question_id, version).await
+    }
+
+    /// Brings a soft deleted question back. Only admins may restore questions.
+    pub async fn restore(
+        &self,
+        principal: &Principal,
+        question_id: &QuestionId,
+    ) -> Result<QuestionEntity, CoreError> {
+        principal.require(Role::Admin)?;
+        self.question_port.restore(question_id).await
+    }
+
+    /// Permanently removes a soft deleted question. Only admins may purge questions.
+    pub async fn purge(
+        &self,
+        principal: &Principal,
+        question_id: &QuestionId,
+    ) -> Result<(), CoreError> {
+        principal.require(Role::Admin)?;
+        self.question_port.purge(question_id).await
+    }
+
+    /// Lists the revisions of a question, oldest first. Deleted questions have no visible
+    /// history.
+    pub async fn revisions(
+        &self,
+        question_id: &QuestionId,
+    ) -> Result<Vec<QuestionRevisionEntity>, CoreError> {
+        self.question_port.get(question_id).await?;
+        self.question_revision_port.list(question_id).await
+    }
+
+    /// Gets revision `revision` of a question, along with the fields it changed compared to the
+    /// revision before it.
+    pub async fn revision(
+        &self,
+        question_id: &QuestionId,
+        revision: i32,
+    ) -> Result<(QuestionRevisionEntity, Vec<RevisionChangeEntity>), CoreError> {
+        let mut revisions = self.revisions(question_id).await?;
+        let position = revisions
+            .iter()
+            .position(|candidate| candidate.revision == revision)
+            .ok_or(CoreError::NotFound)?;
+        let previous = position.checked_sub(1).map(|previous| &revisions[previous]);
+        let changes = revisions[position].diff(previous);
+        Ok((revisions.swap_remove(position), changes))
+    }
+
+    /// Writes the title, content and tags of revision `revision` back to the question as a new
+    /// update, so the revert itself shows up in the history.
+    ///
+    /// The update is based on `version` when given and on the current version otherwise, failing
+    /// with `CoreError::Conflict` when it is stale.
+    pub async fn revert(
+        &self,
+        question_id: &QuestionId,
+        revision: i32,
+        version: Option<i32>,
+    ) -> Result<QuestionEntity, CoreError> {
+        let question = self.question_port.get(question_id).await?;
+        let revision = self
+            .question_revision_port
+            .get(question_id, revision)
+            .await?;
+
+        self.update(QuestionEntity {
+            title: revision.title,
+            content: revision.content,
+            tags: revision.tags,
+            version: version.unwrap_or(question.version),
+            ..question
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+    use crate::services::fakes::{FakeQuestionPort, FakeQuestionRevisionPort};
+
+    fn question() -> QuestionEntity {
+        QuestionEntity::new(
+            QuestionId::generate(),
+            "How to test services?".to_string(),
+            "With fake ports".to_string(),
+            Some(vec!["rust".to_string()]),
+        )
+    }
+
+    fn revision(question: &QuestionEntity, revision: i32, title: &str) -> QuestionRevisionEntity {
+        QuestionRevisionEntity {
+            question_id: question.id.clone(),
+            revision,
+            title: title.to_string(),
+            content: question.content.clone(),
+            tags: question.tags.clone(),
+            created_on: Utc::now(),
+        }
+    }
+
+    fn service(
+        question_port: Arc<FakeQuestionPort>,
+        revisions: Vec<QuestionRevisionEntity>,
+    ) -> QuestionService {
+        QuestionService::new(question_port, Arc::new(FakeQuestionRevisionPort(revisions)))
+    }
+
+    fn admin() -> Principal {
+        Principal::Authenticated {
+            subject: "admin".to_string(),
+            role: Role::Admin,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_add_validates_before_storing() {
+        let question_port = Arc::new(FakeQuestionPort::default());
+        let question_service = service(question_port.clone(), vec![]);
+
+        let invalid = CreateQuestionEntity {
+            title: " ".to_string(),
+            content: "content".to_string(),
+            tags: None,
+        };
+        assert!(matches!(
+            question_service.add(invalid).await,
+            Err(CoreError::Validation(_))
+        ));
+        assert!(question_port.calls.lock().unwrap().is_empty());
+
+        let valid = CreateQuestionEntity {
+            title: "title".to_string(),
+            content: "content".to_string(),
+            tags: None,
+        };
+        let added = question_service.add(valid).await.unwrap();
+        assert_eq!(question_service.get(&added.id).await.unwrap(), added);
+    }
+
+    #[tokio::test]
+    async fn test_list_searches_only_with_a_search_term() {
+        let question_port = Arc::new(FakeQuestionPort::with(vec![question()]));
+        let question_service = service(question_port.clone(), vec![]);
+
+        let (page, total) = question_service
+            .list(&QuestionFilter::default())
+            .await
+            .unwrap();
+        assert_eq!((page.items.len(), total), (1, 1));
+
+        let search = QuestionFilter {
+            search: Some("services".to_string()),
+            ..Default::default()
+        };
+        question_service.list(&search).await.unwrap();
+        assert_eq!(
+            *question_port.calls.lock().unwrap(),
+            vec!["list", "count", "search", "count"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_restore_and_purge_require_an_admin() {
+        let mut deleted = question();
+        deleted.deleted_on = Some(Utc::now());
+        let question_port = Arc::new(FakeQuestionPort::with(vec![deleted.clone()]));
+        let question_service = service(question_port.clone(), vec![]);
+        let member = Principal::Authenticated {
+            subject: "member".to_string(),
+            role: Role::Member,
+        };
+
+        assert!(matches!(
+            question_service
+                .restore(&Principal::Anonymous, &deleted.id)
+                .await,
+            Err(CoreError::Unauthorized(_))
+        ));
+        assert!(matches!(
+            question_service.purge(&member, &deleted.id).await,
+            Err(CoreError::Forbidden(_))
+        ));
+        assert!(question_port.calls.lock().unwrap().is_empty());
+
+        let restored = question_service
+            .restore(&admin(), &deleted.id)
+            .await
+            .unwrap();
+        assert_eq!(restored.deleted_on, None);
+        question_service.purge(&admin(), &deleted.id).await.unwrap();
+        assert!(matches!(
+            question_service.get(&deleted.id).await,
+            Err(CoreError::NotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_revisions_of_a_deleted_question_are_hidden() {
+        let mut deleted = question();
+        deleted.deleted_on = Some(Utc::now());
+        let revisions = vec![revision(&deleted, 1, "first")];
+        let question_service = service(
+            Arc::new(FakeQuestionPort::with(vec![deleted.clone()])),
+            revisions,
+        );
+
+        assert!(matches!(
+            question_service.revisions(&deleted.id).await,
+            Err(CoreError::NotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_revision_lists_changes_from_the_previous_revision() {
+        let question = question();
+        let revisions = vec![
+            revision(&question, 1, "first"),
+            revision(&question, 2, "second"),
+        ];
+        let question_service = service(
+            Arc::new(FakeQuestionPort::with(vec![question.clone()])),
+            revisions,
+        );
+
+        let (second, changes) = question_service.revision(&question.id, 2).await.unwrap();
+        assert_eq!(second.title, "second");
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].field, "title");
+
+        assert!(matches!(
+            question_service.revision(&question.id, 3).await,
+            Err(CoreError::NotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_revert_updates_from_the_current_version() {
+        let mut question = question();
+        question.version = 2;
+        let revisions = vec![
+            revision(&question, 1, "first"),
+            revision(&question, 2, &question.title),
+        ];
+        let question_service = service(
+            Arc::new(FakeQuestionPort::with(vec![question.clone()])),
+            revisions,
+        );
+
+        let reverted = question_service
+            .revert(&question.id, 1, None)
+            .await
+            .unwrap();
+        assert_eq!(reverted.title, "first");
+        assert_eq!(reverted.version, 3);
+
+        assert!(matches!(
+            question_service.revert(&question.id, 1, Some(2)).await,
+            Err(CoreError::Conflict(_))
+        ));
+    }
+}
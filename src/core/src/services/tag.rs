@@ -0,0 +1,45 @@
+use std::sync::Arc;
+
+use crate::common::errors::CoreError;
+use crate::entities::tag::TagEntity;
+use crate::ports::tag::TagPort;
+
+/// Use cases around the tags carried by questions.
+#[derive(Clone)]
+pub struct TagService {
+    tag_port: Arc<dyn TagPort + Send + Sync>,
+}
+
+impl TagService {
+    pub fn new(tag_port: Arc<dyn TagPort + Send + Sync>) -> Self {
+        TagService { tag_port }
+    }
+
+    /// Lists the tags carried by questions that are not deleted, most used first.
+    pub async fn list(&self) -> Result<Vec<TagEntity>, CoreError> {
+        self.tag_port.list().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::fakes::FakeTagPort;
+
+    #[tokio::test]
+    async fn test_list_tags() {
+        let tags = vec![
+            TagEntity {
+                name: "rust".to_string(),
+                count: 2,
+            },
+            TagEntity {
+                name: "warp".to_string(),
+                count: 1,
+            },
+        ];
+        let tag_service = TagService::new(Arc::new(FakeTagPort(tags.clone())));
+
+        assert_eq!(tag_service.list().await.unwrap(), tags);
+    }
+}
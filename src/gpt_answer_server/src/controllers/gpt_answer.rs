@@ -3,6 +3,7 @@ use std::sync::Arc;
 use tonic::{Request, Response, Status};
 use tracing::instrument;
 
+use common::errors::grpc_status;
 use common::grpc::gpt_answer::gpt_answer::{
     gpt_answer_service_server::GptAnswerService, GetAnswerPayload, GetAnswerResponse,
 };
@@ -57,15 +58,10 @@ impl GptAnswerService for GptAnswerServiceImpl {
                     self.cache
                         .set(&payload.question.clone(), &default_answer.clone(), None)
                         .await
-                        .map_err(|err| {
-                            Status::internal(format!("failed to get answer from cache: {}", err))
-                        })?;
+                        .map_err(|err| grpc_status(&err))?;
                     default_answer
                 } else {
-                    return Err(Status::internal(format!(
-                        "failed to get answer from cache: {}",
-                        err
-                    )));
+                    return Err(grpc_status(&err));
                 }
             }
         };
@@ -8,22 +8,36 @@ use tonic::transport::Server;
 use tracing::info;
 
 use adapter::repositories::redis::cache::RedisCache;
+use adapter::repositories::redis::tiered_cache::TieredCache;
 use common::grpc::gpt_answer::gpt_answer::gpt_answer_service_server::GptAnswerServiceServer;
 use common::kill_signals;
 use common::loggers::telemetry::init_telemetry;
 use common::options::parse_options;
 use gpt_answer_server::controllers::gpt_answer::GptAnswerServiceImpl;
 use gpt_answer_server::options::Options;
+use rust_core::ports::cache::CachePort;
 
 pub async fn serve(options: Options, rx: Receiver<()>) {
     let address = options.server_endpoint.parse().unwrap();
     println!("Starting GPT Answer server at {}", options.server_endpoint);
 
-    let cache = RedisCache::new(&options.redis.host, options.redis.port)
-        .await
-        .unwrap();
+    let cache: Arc<dyn CachePort + Sync + Send> = match &options.tiered_cache {
+        Some(tiered_cache) => {
+            info!("Caching answers in process in front of redis");
+            Arc::new(
+                TieredCache::new(&options.redis, tiered_cache)
+                    .await
+                    .unwrap(),
+            )
+        }
+        None => Arc::new(
+            RedisCache::new(&options.redis.host, options.redis.port)
+                .await
+                .unwrap(),
+        ),
+    };
 
-    let gpt_answer_service = GptAnswerServiceImpl::new(Arc::new(cache));
+    let gpt_answer_service = GptAnswerServiceImpl::new(cache);
 
     Server::builder()
         .add_service(GptAnswerServiceServer::new(gpt_answer_service))
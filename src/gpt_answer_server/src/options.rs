@@ -1,5 +1,7 @@
 use serde::Deserialize;
 
+use adapter::repositories::redis::config::RedisConfig;
+use adapter::repositories::redis::tiered_cache::TieredCacheConfig;
 use common::options::{default_log, Log};
 
 /// Configuration options for the application.
@@ -17,16 +19,9 @@ pub struct Options {
     pub service_name: String,
     /// Configuration for redis.
     pub redis: RedisConfig,
+    /// Configuration for caching answers in process in front of redis, disabled when absent.
+    pub tiered_cache: Option<TieredCacheConfig>,
     /// Configuration for logging, including log level.
     #[serde(default = "default_log")]
     pub log: Log,
 }
-
-/// Represents redis configuration.
-#[derive(Debug, Deserialize, Clone)]
-pub struct RedisConfig {
-    /// Port number for the redis.
-    pub port: u16,
-    /// Host for the redis.
-    pub host: String,
-}
@@ -0,0 +1,82 @@
+use std::sync::Arc;
+
+use subtle::ConstantTimeEq;
+use warp::{Filter, Rejection};
+
+use rust_core::common::errors::CoreError;
+use rust_core::entities::principal::Principal;
+
+use crate::errors::WarpError;
+use crate::options::ApiToken;
+
+/// Filter resolving the `Authorization: Bearer <token>` header to the principal a request runs
+/// on behalf of.
+///
+/// Requests without the header are anonymous, and the services decide whether that is enough.
+/// A header carrying anything but one of `tokens` is rejected with status code 401.
+pub fn principal(
+    tokens: Arc<Vec<ApiToken>>,
+) -> impl Filter<Extract = (Principal,), Error = Rejection> + Clone {
+    warp::header::optional::<String>("authorization").and_then(
+        move |authorization: Option<String>| {
+            let tokens = tokens.clone();
+            async move {
+                resolve(&tokens, authorization.as_deref())
+                    .map_err(|err| warp::reject::custom(WarpError::from(err)))
+            }
+        },
+    )
+}
+
+/// Finds the caller presenting the bearer token in `authorization`.
+///
+/// Tokens are compared in constant time, so response times do not tell how much of a guess
+/// matched.
+fn resolve(tokens: &[ApiToken], authorization: Option<&str>) -> Result<Principal, CoreError> {
+    let Some(authorization) = authorization else {
+        return Ok(Principal::Anonymous);
+    };
+    let token = authorization
+        .strip_prefix("Bearer ")
+        .map(str::trim)
+        .ok_or_else(|| CoreError::Unauthorized("expected a bearer token".to_string()))?;
+    tokens
+        .iter()
+        .find(|candidate| bool::from(candidate.token.as_bytes().ct_eq(token.as_bytes())))
+        .map(|candidate| Principal::Authenticated {
+            subject: candidate.subject.clone(),
+            role: candidate.role,
+        })
+        .ok_or_else(|| CoreError::Unauthorized("unknown bearer token".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_core::entities::principal::Role;
+
+    use super::*;
+
+    #[test]
+    fn test_resolve() {
+        let tokens = vec![ApiToken {
+            subject: "ops".to_string(),
+            token: "secret".to_string(),
+            role: Role::Admin,
+        }];
+
+        assert_eq!(resolve(&tokens, None).unwrap(), Principal::Anonymous);
+        assert_eq!(
+            resolve(&tokens, Some("Bearer secret")).unwrap(),
+            Principal::Authenticated {
+                subject: "ops".to_string(),
+                role: Role::Admin,
+            }
+        );
+        for invalid in ["Bearer other", "Bearer secret2", "Basic secret", "secret"] {
+            match resolve(&tokens, Some(invalid)) {
+                Err(CoreError::Unauthorized(_)) => {}
+                other => panic!("Expected Unauthorized error, but got {:?}", other),
+            }
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use std::str::FromStr;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+use warp::filters::path::FullPath;
+use warp::http::header::LOCATION;
+use warp::http::StatusCode;
+use warp::reject::Rejection;
+use warp::Reply;
+
+use rust_core::entities::answer::AnswerId;
+use rust_core::entities::question::QuestionId;
+use rust_core::services::answer::AnswerService;
+
+use crate::errors::WarpError;
+
+/// Body of `POST /questions/{id}/answers`.
+///
+/// An answer written by a person carries its `body`; leaving it out asks the GPT answer service
+/// to generate one.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct AddAnswerRequest {
+    /// Text of the answer, absent to generate it.
+    #[serde(default)]
+    pub body: Option<String>,
+}
+
+/// Handler for listing the answers to a question.
+///
+/// This function returns every stored answer to the question with the specified ID as JSON,
+/// oldest first. Deleted questions have no visible answers.
+#[instrument(level = "info", skip(answer_service))]
+pub async fn get_question_answers(
+    answer_service: Arc<AnswerService>,
+    id: String,
+) -> Result<impl Reply, Rejection> {
+    let question_id = QuestionId::from_str(id.as_str()).map_err(WarpError::from)?;
+
+    let answers = answer_service
+        .list(&question_id)
+        .await
+        .map_err(WarpError::from)?;
+
+    Ok(warp::reply::json(&answers))
+}
+
+/// Handler for getting one answer to a question.
+///
+/// This function returns the answer with the specified ID as JSON, or status code 404 if it
+/// does not belong to the question.
+#[instrument(level = "info", skip(answer_service))]
+pub async fn get_question_answer(
+    answer_service: Arc<AnswerService>,
+    id: String,
+    answer_id: String,
+) -> Result<impl Reply, Rejection> {
+    let question_id = QuestionId::from_str(id.as_str()).map_err(WarpError::from)?;
+    let answer_id = AnswerId::from_str(answer_id.as_str()).map_err(WarpError::from)?;
+
+    let answer = answer_service
+        .get(&question_id, &answer_id)
+        .await
+        .map_err(WarpError::from)?;
+
+    Ok(warp::reply::json(&answer))
+}
+
+/// Handler for adding an answer to a question.
+///
+/// This function stores the answer given in the request body, or has the GPT answer service
+/// generate one when the body has none. It returns the stored answer with status code 201 and a
+/// `Location` header pointing at it, or status code 422 when the given answer is blank.
+#[instrument(level = "info", skip(answer_service))]
+pub async fn add_question_answer(
+    answer_service: Arc<AnswerService>,
+    id: String,
+    path: FullPath,
+    request: AddAnswerRequest,
+) -> Result<impl Reply, Rejection> {
+    let question_id = QuestionId::from_str(id.as_str()).map_err(WarpError::from)?;
+
+    let answer = match request.body {
+        Some(body) => answer_service.add(&question_id, body).await,
+        None => answer_service.generate(&question_id).await,
+    }
+    .map_err(WarpError::from)?;
+    let location = format!("{}/{}", path.as_str().trim_end_matches('/'), answer.id);
+
+    Ok(warp::reply::with_header(
+        warp::reply::with_status(warp::reply::json(&answer), StatusCode::CREATED),
+        LOCATION,
+        location,
+    ))
+}
@@ -1 +1,4 @@
+pub mod answer;
 pub mod question;
+pub mod question_revision;
+pub mod tag;
@@ -2,150 +2,459 @@ use std::collections::HashMap;
 use std::str::FromStr;
 use std::sync::Arc;
 
+use serde::{Deserialize, Serialize};
 use tracing::instrument;
+use warp::filters::path::FullPath;
+use warp::http::header::{ETAG, LINK, LOCATION};
 use warp::http::StatusCode;
 use warp::reject::Rejection;
 use warp::Reply;
 
-use adapter::repositories::grpc::gpt_answer_client::GptAnswerClient;
-use rust_core::entities::question::{QuestionEntity, QuestionId};
+use rust_core::common::errors::CoreError;
+use rust_core::entities::pagination_entity::PaginationEntity;
+use rust_core::entities::principal::Principal;
+use rust_core::entities::question::{CreateQuestionEntity, QuestionEntity, QuestionId};
 use rust_core::entities::question_filter::QuestionFilter;
-use rust_core::ports::gpt_answer::GptAnswerPort;
-use rust_core::ports::question::QuestionPort;
+use rust_core::services::question::QuestionService;
 
 use crate::errors::WarpError;
 
+/// Page of questions returned by `GET /questions`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QuestionPage {
+    /// Questions in the page.
+    pub items: Vec<QuestionEntity>,
+    /// Number of questions matching the filter across all pages.
+    pub total: usize,
+    /// Position of the first item within the filtered questions, absent in cursor mode.
+    pub start: Option<usize>,
+    /// Position right after the last item, absent in cursor mode.
+    pub end: Option<usize>,
+    /// Opaque cursor of the following page, absent on the last page.
+    pub next_cursor: Option<String>,
+}
+
+/// Builds the RFC 8288 `Link` header value pointing at the next and previous pages.
+///
+/// Offset pages link to neighbouring `start`/`end` windows of the same size, while cursor pages
+/// only link forward through `next_cursor`. Every other query parameter is carried over as is.
+fn pagination_links(
+    path: &str,
+    query: &HashMap<String, String>,
+    pagination: &PaginationEntity,
+    returned: usize,
+    total: usize,
+    next_cursor: Option<&str>,
+) -> Option<String> {
+    let link = |overrides: Vec<(&str, String)>, rel: &str| {
+        let mut params = query.clone();
+        for (key, value) in overrides {
+            params.insert(key.to_string(), value);
+        }
+        let mut params = params.into_iter().collect::<Vec<_>>();
+        params.sort();
+        let query_string = serde_urlencoded::to_string(params).unwrap_or_default();
+        format!("<{path}?{query_string}>; rel=\"{rel}\"")
+    };
+
+    let page_size = pagination.limit();
+    let mut links = Vec::new();
+    match (&pagination.cursor, next_cursor) {
+        (Some(_), Some(cursor)) => links.push(link(vec![("cursor", cursor.to_string())], "next")),
+        (Some(_), None) => {}
+        (None, _) => {
+            if pagination.start + returned < total {
+                let start = pagination.start + page_size;
+                links.push(link(
+                    vec![
+                        ("start", start.to_string()),
+                        ("end", (start + page_size).to_string()),
+                    ],
+                    "next",
+                ));
+            }
+            if pagination.start > 0 {
+                links.push(link(
+                    vec![
+                        (
+                            "start",
+                            pagination.start.saturating_sub(page_size).to_string(),
+                        ),
+                        ("end", pagination.start.to_string()),
+                    ],
+                    "prev",
+                ));
+            }
+        }
+    }
+
+    (!links.is_empty()).then(|| links.join(", "))
+}
+
 /// Handler for retrieving questions based on query parameters.
 ///
 /// This function retrieves questions based on the provided query parameters. It takes a HashMap
-/// containing the query parameters and a reference to the QuestionPort trait object. It returns
-/// a JSON response containing the list of questions.
-#[instrument(level = "info", skip(question_port))]
+/// containing the query parameters and the QuestionService. It returns a JSON `QuestionPage`
+/// envelope with the questions, the total number of matches and the `next_cursor` to pass back as
+/// the `cursor` query parameter, along with a `Link` header pointing at the neighbouring pages.
+/// With a `q` query parameter the questions matching the search are returned most relevant first.
+#[instrument(level = "info", skip(question_service))]
 pub async fn get_questions(
-    question_port: Arc<dyn QuestionPort + Send + Sync>,
+    question_service: Arc<QuestionService>,
+    path: FullPath,
     query: HashMap<String, String>,
 ) -> Result<impl Reply, Rejection> {
-    let question_filter = QuestionFilter::try_from(query).map_err(WarpError::from)?;
+    let question_filter = QuestionFilter::try_from(query.clone()).map_err(WarpError::from)?;
 
-    let questions = question_port
+    let (page, total) = question_service
         .list(&question_filter)
         .await
         .map_err(WarpError::from)?;
 
-    Ok(warp::reply::json(&questions))
+    let pagination = &question_filter.pagination;
+    let (start, end) = match pagination.cursor {
+        Some(_) => (None, None),
+        None => (
+            Some(pagination.start),
+            Some(pagination.start + page.items.len()),
+        ),
+    };
+    let links = pagination_links(
+        path.as_str(),
+        &query,
+        pagination,
+        page.items.len(),
+        total,
+        page.next_cursor.as_deref(),
+    );
+    let reply = warp::reply::json(&QuestionPage {
+        items: page.items,
+        total,
+        start,
+        end,
+        next_cursor: page.next_cursor,
+    });
+
+    Ok(match links {
+        Some(links) => warp::reply::with_header(reply, LINK, links).into_response(),
+        None => reply.into_response(),
+    })
 }
 
 /// Handler for retrieving a question by ID.
 ///
 /// This function retrieves a question with the specified ID from the system. It takes the ID of
-/// the question to be got as a string and a reference to the QuestionPort trait object. It returns
-/// a JSON response containing the question.
-#[instrument(level = "info", skip(question_port))]
+/// the question to be got as a string and the QuestionService. It returns a JSON response
+/// containing the question, with its version as the `ETag`.
+#[instrument(level = "info", skip(question_service))]
 pub async fn get_question(
-    question_port: Arc<dyn QuestionPort + Send + Sync>,
+    question_service: Arc<QuestionService>,
     id: String,
 ) -> Result<impl Reply, Rejection> {
     let question_id = QuestionId::from_str(id.as_str()).map_err(WarpError::from)?;
 
-    let question = question_port
+    let question = question_service
         .get(&question_id)
         .await
         .map_err(WarpError::from)?;
 
-    Ok(warp::reply::json(&question))
+    Ok(warp::reply::with_header(
+        warp::reply::json(&question),
+        ETAG,
+        etag(question.version),
+    ))
 }
 
 /// Handler for adding a new question.
 ///
-/// This function adds a new question to the system. It takes a QuestionEntity representing the
-/// question to be added and a reference to the QuestionPort trait object. It returns a success
-/// response with status code 200 if the question is added successfully.
-#[instrument(level = "info", skip(question_port))]
+/// This function adds a new question to the system. It takes a CreateQuestionEntity representing
+/// the question to be added and the QuestionService, which validates it; the repository allocates
+/// the ID. It returns the stored question with status code 201 and a `Location` header pointing at
+/// it, or status code 422 listing the invalid fields.
+#[instrument(level = "info", skip(question_service))]
 pub async fn add_question(
-    question_port: Arc<dyn QuestionPort + Send + Sync>,
-    question: QuestionEntity,
+    question_service: Arc<QuestionService>,
+    path: FullPath,
+    question: CreateQuestionEntity,
 ) -> Result<impl Reply, Rejection> {
-    question_port.add(question).await.map_err(WarpError::from)?;
+    let question = question_service
+        .add(question)
+        .await
+        .map_err(WarpError::from)?;
+    let location = format!("{}/{}", path.as_str().trim_end_matches('/'), question.id);
 
-    Ok(warp::reply::with_status("Question added", StatusCode::OK))
+    Ok(warp::reply::with_header(
+        warp::reply::with_status(warp::reply::json(&question), StatusCode::CREATED),
+        LOCATION,
+        location,
+    ))
 }
 
 /// Handler for deleting a question by ID.
 ///
 /// This function deletes a question with the specified ID from the system. It takes the ID of
-/// the question to be deleted as a string and a reference to the QuestionPort trait object. It
-/// returns a success response with status code 200 if the question is deleted successfully.
-#[instrument(level = "info", skip(question_port))]
+/// the question to be deleted as a string, the optional `If-Match` header and the
+/// QuestionService. It returns a success response with status code 200 if the question is deleted
+/// successfully, or status code 412 if `If-Match` names an outdated version.
+#[instrument(level = "info", skip(question_service))]
 pub async fn delete_question(
-    question_port: Arc<dyn QuestionPort + Send + Sync>,
+    question_service: Arc<QuestionService>,
     id: String,
+    if_match: Option<String>,
 ) -> Result<impl Reply, Rejection> {
     let question_id = QuestionId::from_str(id.as_str()).map_err(WarpError::from)?;
+    let expected_version =
+        if_match_version(&question_service, &question_id, if_match.as_deref()).await?;
 
-    question_port
-        .delete(&question_id)
+    question_service
+        .delete(&question_id, expected_version)
         .await
-        .map_err(WarpError::from)?;
+        .map_err(precondition_error)?;
 
     Ok(warp::reply::with_status("Question deleted", StatusCode::OK))
 }
 
-/// Handler for updating a question by ID.
+/// Handler for restoring a deleted question by ID.
 ///
-/// This function updates a question with the specified ID in the system. It takes the ID of the
-/// question to be updated as a string, the updated QuestionEntity, and a reference to the
-/// QuestionPort trait object. It returns a success response with status code 200 if the question
-/// is updated successfully.
-#[instrument(level = "info", skip(question_port))]
-pub async fn update_question(
-    question_port: Arc<dyn QuestionPort + Send + Sync>,
+/// This function brings back a soft deleted question. It takes the ID of the question to be
+/// restored as a string, the calling Principal and the QuestionService. It returns the restored
+/// question as JSON with its version as the `ETag`, or status code 401 or 403 unless the caller is
+/// an admin.
+#[instrument(level = "info", skip(question_service))]
+pub async fn restore_question(
+    question_service: Arc<QuestionService>,
     id: String,
-    mut question: QuestionEntity,
+    principal: Principal,
 ) -> Result<impl Reply, Rejection> {
-    question.id = QuestionId::from_str(id.as_str()).map_err(WarpError::from)?;
+    let question_id = QuestionId::from_str(id.as_str()).map_err(WarpError::from)?;
 
-    question_port
-        .update(question)
+    let question = question_service
+        .restore(&principal, &question_id)
         .await
         .map_err(WarpError::from)?;
 
-    Ok(warp::reply::with_status("Question updated", StatusCode::OK))
+    Ok(warp::reply::with_header(
+        warp::reply::json(&question),
+        ETAG,
+        etag(question.version),
+    ))
 }
 
-/// Controller for handling HTTP GET requests to fetch answers for a given question ID.
-///
-/// This controller retrieves a question from the provided `QuestionPort` based on the
-/// specified ID, calls the gRPC client (`GptAnswerClient`) to get an answer using the
-/// question's content, and responds with the answer in a JSON format.
-///
-/// # Arguments
-///
-/// * `question_port`: A trait object implementing `QuestionPort` for interacting with questions.
-/// * `gpt_answer_client`: The gRPC client instance for answering questions.
-/// * `id`: The ID of the question to fetch the answer for.
-///
-/// # Returns
+/// Handler for purging a deleted question by ID.
 ///
-/// Returns a `Result` containing the HTTP response. If successful, responds with the answer
-/// as a JSON string and a status code of `200 OK`. If there's an error during
-/// question retrieval, gRPC communication, or response construction, it returns a Warp `Rejection`.
-#[instrument(level = "info", skip(question_port, gpt_answer_client))]
-pub async fn get_question_answer(
-    question_port: Arc<dyn QuestionPort + Send + Sync>,
-    gpt_answer_client: Arc<GptAnswerClient>,
+/// This function permanently removes a soft deleted question. It takes the ID of the question to
+/// be purged as a string, the calling Principal and the QuestionService. It returns a success
+/// response with status code 200 if the question is purged, status code 401 or 403 unless the
+/// caller is an admin, or status code 409 if it has not been deleted first.
+#[instrument(level = "info", skip(question_service))]
+pub async fn purge_question(
+    question_service: Arc<QuestionService>,
     id: String,
+    principal: Principal,
 ) -> Result<impl Reply, Rejection> {
-    let question_id = QuestionId::from_str(&id).map_err(WarpError::from)?;
+    let question_id = QuestionId::from_str(id.as_str()).map_err(WarpError::from)?;
 
-    let question = question_port
-        .get(&question_id)
+    question_service
+        .purge(&principal, &question_id)
         .await
         .map_err(WarpError::from)?;
 
-    let answer = gpt_answer_client
-        .get_answer(&question.content)
-        .await
-        .map_err(WarpError::from)?;
+    Ok(warp::reply::with_status("Question purged", StatusCode::OK))
+}
+
+/// Handler for updating a question by ID.
+///
+/// This function updates a question with the specified ID in the system. It takes the ID of the
+/// question to be updated as a string, the optional `If-Match` header, the updated QuestionEntity,
+/// and the QuestionService. The version being edited comes from `If-Match` when present and from
+/// the body otherwise. It returns a success response with status code 200 and the new `ETag` if the
+/// question is updated successfully. A stale version is answered with status code 412 when it came
+/// from `If-Match`, and 409 when it came from the body. Invalid fields are answered with status
+/// code 422.
+#[instrument(level = "info", skip(question_service))]
+pub async fn update_question(
+    question_service: Arc<QuestionService>,
+    id: String,
+    if_match: Option<String>,
+    mut question: QuestionEntity,
+) -> Result<impl Reply, Rejection> {
+    question.id = QuestionId::from_str(id.as_str()).map_err(WarpError::from)?;
+    let expected_version =
+        if_match_version(&question_service, &question.id, if_match.as_deref()).await?;
+    if let Some(expected_version) = expected_version {
+        question.version = expected_version;
+    }
+
+    let question =
+        question_service
+            .update(question)
+            .await
+            .map_err(|err| match expected_version {
+                Some(_) => precondition_error(err),
+                None => WarpError::from(err),
+            })?;
+
+    Ok(warp::reply::with_header(
+        warp::reply::with_status("Question updated", StatusCode::OK),
+        ETAG,
+        etag(question.version),
+    ))
+}
+
+/// Entity tag advertising a question version.
+pub(crate) fn etag(version: i32) -> String {
+    format!("\"{}\"", version)
+}
+
+/// Reads the versions a client accepts from its `If-Match` header.
+///
+/// Returns `None` when the header is absent or `*`. The header may list several entity tags, any
+/// of which matches. Weak tags never match, as `If-Match` uses strong comparison.
+pub(crate) fn if_match_versions(if_match: Option<&str>) -> Result<Option<Vec<i32>>, WarpError> {
+    let tags = match if_match.map(str::trim) {
+        None | Some("*") => return Ok(None),
+        Some(tags) => tags,
+    };
+    let mut versions = vec![];
+    for tag in tags.split(',').map(str::trim).filter(|tag| !tag.is_empty()) {
+        if tag.starts_with("W/") {
+            continue;
+        }
+        let version = tag
+            .strip_prefix('"')
+            .and_then(|tag| tag.strip_suffix('"'))
+            .and_then(|version| version.parse().ok())
+            .ok_or_else(|| {
+                WarpError::from(CoreError::InvalidParameter(format!(
+                    "If-Match: {tag} is not a question entity tag"
+                )))
+            })?;
+        versions.push(version);
+    }
+    if versions.is_empty() {
+        return Err(WarpError::PreconditionFailed(format!(
+            "weak entity tags {tags} never match"
+        )));
+    }
+    Ok(Some(versions))
+}
+
+/// Picks the version a write guarded by `If-Match` expects.
+///
+/// A single listed version is checked by the write itself. When several are listed, the current
+/// version is looked up and must be one of them, the write still failing if it changes meanwhile.
+pub(crate) async fn if_match_version(
+    question_service: &QuestionService,
+    question_id: &QuestionId,
+    if_match: Option<&str>,
+) -> Result<Option<i32>, WarpError> {
+    let versions = match if_match_versions(if_match)? {
+        None => return Ok(None),
+        Some(versions) if versions.len() == 1 => return Ok(Some(versions[0])),
+        Some(versions) => versions,
+    };
+    let question = question_service.get(question_id).await?;
+    if versions.contains(&question.version) {
+        Ok(Some(question.version))
+    } else {
+        Err(WarpError::PreconditionFailed(format!(
+            "question is at version {}",
+            question.version
+        )))
+    }
+}
+
+/// Reports a version conflict on a request guarded by `If-Match` as a failed precondition.
+pub(crate) fn precondition_error(err: CoreError) -> WarpError {
+    match err {
+        CoreError::Conflict(message) => WarpError::PreconditionFailed(message),
+        err => WarpError::from(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_core::entities::cursor_entity::CursorEntity;
+
+    use super::*;
+
+    #[test]
+    fn test_if_match_versions() {
+        assert_eq!(if_match_versions(None).unwrap(), None);
+        assert_eq!(if_match_versions(Some("*")).unwrap(), None);
+        assert_eq!(if_match_versions(Some(&etag(3))).unwrap(), Some(vec![3]));
+        assert_eq!(
+            if_match_versions(Some("\"3\", W/\"4\",\"5\"")).unwrap(),
+            Some(vec![3, 5])
+        );
+        assert!(matches!(
+            if_match_versions(Some("W/\"3\"")),
+            Err(WarpError::PreconditionFailed(_))
+        ));
+        assert!(matches!(
+            if_match_versions(Some("3")),
+            Err(WarpError::CoreError(CoreError::InvalidParameter(_)))
+        ));
+        assert!(matches!(
+            if_match_versions(Some("\"3\", *")),
+            Err(WarpError::CoreError(CoreError::InvalidParameter(_)))
+        ));
+    }
+
+    fn query(params: &[(&str, &str)]) -> HashMap<String, String> {
+        params
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_pagination_links_offset() {
+        let query = query(&[("start", "10"), ("end", "20"), ("tags", "rust,warp")]);
+        let pagination = PaginationEntity::try_from(&query).unwrap();
+
+        let links = pagination_links("/questions", &query, &pagination, 10, 35, Some("abc"));
+        assert_eq!(
+            links.as_deref(),
+            Some(
+                "</questions?end=30&start=20&tags=rust%2Cwarp>; rel=\"next\", \
+                 </questions?end=10&start=0&tags=rust%2Cwarp>; rel=\"prev\""
+            )
+        );
+
+        // The last page has no next link and the first page no previous link
+        let links = pagination_links("/questions", &query, &pagination, 5, 15, None);
+        assert_eq!(
+            links.as_deref(),
+            Some("</questions?end=10&start=0&tags=rust%2Cwarp>; rel=\"prev\"")
+        );
+        let first_page = PaginationEntity::default();
+        assert_eq!(
+            pagination_links("/questions", &HashMap::new(), &first_page, 3, 3, None),
+            None
+        );
+    }
+
+    #[test]
+    fn test_pagination_links_cursor() {
+        let cursor = CursorEntity {
+            sort: vec![],
+            values: vec![],
+            id: "1".to_string(),
+        }
+        .encode()
+        .unwrap();
+        let query = query(&[("cursor", cursor.as_str())]);
+        let pagination = PaginationEntity::try_from(&query).unwrap();
 
-    Ok(warp::reply::with_status(answer, StatusCode::OK))
+        let links = pagination_links("/questions", &query, &pagination, 10, 35, Some("next"));
+        assert_eq!(
+            links.as_deref(),
+            Some("</questions?cursor=next>; rel=\"next\"")
+        );
+        assert_eq!(
+            pagination_links("/questions", &query, &pagination, 10, 35, None),
+            None
+        );
+    }
 }
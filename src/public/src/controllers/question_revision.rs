@@ -0,0 +1,99 @@
+use std::str::FromStr;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+use warp::http::header::ETAG;
+use warp::reject::Rejection;
+use warp::Reply;
+
+use rust_core::entities::question::QuestionId;
+use rust_core::entities::question_revision::{QuestionRevisionEntity, RevisionChangeEntity};
+use rust_core::services::question::QuestionService;
+
+use crate::controllers::question::{etag, if_match_version, precondition_error};
+use crate::errors::WarpError;
+
+/// A revision returned by `GET /questions/{id}/revisions/{n}`, with what it changed.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QuestionRevisionDetail {
+    /// The revision itself.
+    #[serde(flatten)]
+    pub revision: QuestionRevisionEntity,
+    /// Fields that differ from the previous revision.
+    pub changes: Vec<RevisionChangeEntity>,
+}
+
+/// Handler for listing the revisions of a question.
+///
+/// This function returns every revision of the question with the specified ID as JSON, oldest
+/// first. Deleted questions have no visible history.
+#[instrument(level = "info", skip(question_service))]
+pub async fn get_question_revisions(
+    question_service: Arc<QuestionService>,
+    id: String,
+) -> Result<impl Reply, Rejection> {
+    let question_id = QuestionId::from_str(id.as_str()).map_err(WarpError::from)?;
+
+    let revisions = question_service
+        .revisions(&question_id)
+        .await
+        .map_err(WarpError::from)?;
+
+    Ok(warp::reply::json(&revisions))
+}
+
+/// Handler for getting one revision of a question.
+///
+/// This function returns revision `n` of the question with the specified ID as JSON, along with
+/// the fields it changed compared to the revision before it.
+#[instrument(level = "info", skip(question_service))]
+pub async fn get_question_revision(
+    question_service: Arc<QuestionService>,
+    id: String,
+    n: i32,
+) -> Result<impl Reply, Rejection> {
+    let question_id = QuestionId::from_str(id.as_str()).map_err(WarpError::from)?;
+
+    let (revision, changes) = question_service
+        .revision(&question_id, n)
+        .await
+        .map_err(WarpError::from)?;
+
+    Ok(warp::reply::json(&QuestionRevisionDetail {
+        revision,
+        changes,
+    }))
+}
+
+/// Handler for reverting a question to one of its revisions.
+///
+/// This function writes the title, content and tags of revision `n` back to the question as a new
+/// update, so the revert itself shows up in the history. Like a `PUT`, it honours `If-Match` and
+/// answers a stale version with status code 412, or 409 when no `If-Match` was given and the
+/// question changed concurrently. It returns the updated question with its new `ETag`.
+#[instrument(level = "info", skip(question_service))]
+pub async fn revert_question(
+    question_service: Arc<QuestionService>,
+    id: String,
+    n: i32,
+    if_match: Option<String>,
+) -> Result<impl Reply, Rejection> {
+    let question_id = QuestionId::from_str(id.as_str()).map_err(WarpError::from)?;
+    let expected_version =
+        if_match_version(&question_service, &question_id, if_match.as_deref()).await?;
+
+    let question = question_service
+        .revert(&question_id, n, expected_version)
+        .await
+        .map_err(|err| match expected_version {
+            Some(_) => precondition_error(err),
+            None => WarpError::from(err),
+        })?;
+
+    Ok(warp::reply::with_header(
+        warp::reply::json(&question),
+        ETAG,
+        etag(question.version),
+    ))
+}
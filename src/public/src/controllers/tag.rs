@@ -0,0 +1,20 @@
+use std::sync::Arc;
+
+use tracing::instrument;
+use warp::reject::Rejection;
+use warp::Reply;
+
+use rust_core::services::tag::TagService;
+
+use crate::errors::WarpError;
+
+/// Handler for listing tags.
+///
+/// This function returns every tag carried by a question that is not deleted as JSON, along
+/// with the number of questions carrying it, most used first.
+#[instrument(level = "info", skip(tag_service))]
+pub async fn get_tags(tag_service: Arc<TagService>) -> Result<impl Reply, Rejection> {
+    let tags = tag_service.list().await.map_err(WarpError::from)?;
+
+    Ok(warp::reply::json(&tags))
+}
@@ -1,13 +1,22 @@
 use std::io;
 
+use opentelemetry::trace::TraceContextExt;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
+use tracing::error;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use uuid::Uuid;
 use warp::body::BodyDeserializeError;
 use warp::cors::CorsForbidden;
+use warp::http::header::{HeaderValue, CONTENT_TYPE, WWW_AUTHENTICATE};
 use warp::http::StatusCode;
 use warp::reject::Reject;
+use warp::reply::Response;
 use warp::{Rejection, Reply};
 
+use common::errors::http_status;
 use rust_core::common::errors::CoreError;
+use rust_core::common::validation::ValidationErrors;
 
 #[derive(Error, Debug)]
 pub enum WarpError {
@@ -15,56 +24,154 @@ pub enum WarpError {
     CoreError(#[from] CoreError),
     #[error("io error")]
     IOError(#[from] io::Error),
+    #[error("precondition failed {0}")]
+    PreconditionFailed(String),
 }
 
 impl Reject for WarpError {}
 
-pub async fn return_error(r: Rejection) -> Result<impl Reply, Rejection> {
-    if let Some(error) = r.find::<WarpError>() {
-        match error {
-            WarpError::CoreError(e) => match e {
-                CoreError::NotFound => Ok(warp::reply::with_status(
-                    "Not found".to_string(),
-                    StatusCode::NOT_FOUND,
-                )),
-                CoreError::ParseError(_) => Ok(warp::reply::with_status(
-                    "ParseError".to_string(),
-                    StatusCode::BAD_REQUEST,
-                )),
-                CoreError::MissingParameters => Ok(warp::reply::with_status(
-                    "MissingParameters".to_string(),
-                    StatusCode::BAD_REQUEST,
-                )),
-                CoreError::InternalError(_) => Ok(warp::reply::with_status(
-                    "InternalError".to_string(),
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                )),
-                _ => Ok(warp::reply::with_status(
-                    "InternalError".to_string(),
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                )),
-            },
-            _ => Ok(warp::reply::with_status(
-                "InternalError".to_string(),
+/// Media type of error responses.
+pub const PROBLEM_JSON: &str = "application/problem+json";
+
+/// Body of an error response, as described by RFC 7807.
+///
+/// `type` and `title` only depend on the kind of error, so clients can match on them; `detail`
+/// explains this occurrence and is left out for server side failures.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Problem {
+    /// URI identifying the kind of error.
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// Short summary of the kind of error.
+    pub title: String,
+    /// HTTP status code of the response.
+    pub status: u16,
+    /// Explanation specific to this occurrence.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// URI identifying this occurrence, also logged for server side failures.
+    pub instance: String,
+    /// OpenTelemetry trace the failed request belongs to, when it is traced.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace_id: Option<String>,
+    /// Messages for each invalid field, when the request failed validation.
+    #[serde(default, skip_serializing_if = "ValidationErrors::is_empty")]
+    pub errors: ValidationErrors,
+}
+
+impl Problem {
+    fn new(kind: &str, title: &str, status: StatusCode, detail: Option<String>) -> Self {
+        let span_context = tracing::Span::current()
+            .context()
+            .span()
+            .span_context()
+            .clone();
+        Problem {
+            kind: format!("urn:problem-type:{}", kind),
+            title: title.to_string(),
+            status: status.as_u16(),
+            detail,
+            instance: format!("urn:uuid:{}", Uuid::now_v7()),
+            trace_id: span_context
+                .is_valid()
+                .then(|| span_context.trace_id().to_string()),
+            errors: ValidationErrors::default(),
+        }
+    }
+
+    fn into_response(self) -> Response {
+        let status = StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        let mut response = warp::reply::with_header(
+            warp::reply::with_status(warp::reply::json(&self), status),
+            CONTENT_TYPE,
+            PROBLEM_JSON,
+        )
+        .into_response();
+        // Tells the client how to authenticate, as RFC 9110 requires for 401
+        if status == StatusCode::UNAUTHORIZED {
+            response
+                .headers_mut()
+                .insert(WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+        }
+        response
+    }
+}
+
+/// Describes a `CoreError`, leaving out the details of server side failures.
+fn core_problem(e: &CoreError) -> Problem {
+    let status = StatusCode::from_u16(http_status(e)).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    let (kind, title, detail) = match e {
+        CoreError::NotFound => ("not-found", "Not found", None),
+        CoreError::ParseError(err) => ("parse-error", "Parse error", Some(err.to_string())),
+        CoreError::MissingParameters => ("missing-parameters", "Missing parameters", None),
+        CoreError::InvalidParameter(message) => (
+            "invalid-parameter",
+            "Invalid parameter",
+            Some(message.clone()),
+        ),
+        CoreError::Validation(_) => ("validation-failed", "Validation failed", None),
+        CoreError::Conflict(message) => ("conflict", "Conflict", Some(message.clone())),
+        CoreError::Unauthorized(message) => ("unauthorized", "Unauthorized", Some(message.clone())),
+        CoreError::Forbidden(message) => ("forbidden", "Forbidden", Some(message.clone())),
+        CoreError::RateLimited(message) => ("rate-limited", "Rate limited", Some(message.clone())),
+        CoreError::Unavailable(_) => ("unavailable", "Service unavailable", None),
+        CoreError::Timeout(_) => ("timeout", "Timeout", None),
+        CoreError::IOError(_) | CoreError::InternalError(_) | CoreError::UnexpectedResponse(_) => {
+            ("internal-error", "Internal error", None)
+        }
+    };
+    let mut problem = Problem::new(kind, title, status, detail);
+    if let CoreError::Validation(errors) = e {
+        problem.errors = errors.clone();
+    }
+    problem
+}
+
+pub async fn return_error(r: Rejection) -> Result<Response, Rejection> {
+    let problem = if let Some(error) = r.find::<WarpError>() {
+        let problem = match error {
+            WarpError::CoreError(e) => core_problem(e),
+            WarpError::PreconditionFailed(message) => Problem::new(
+                "precondition-failed",
+                "Precondition failed",
+                StatusCode::PRECONDITION_FAILED,
+                Some(message.clone()),
+            ),
+            WarpError::IOError(_) => Problem::new(
+                "internal-error",
+                "Internal error",
                 StatusCode::INTERNAL_SERVER_ERROR,
-            )),
+                None,
+            ),
+        };
+        if problem.status >= 500 {
+            error!(instance = %problem.instance, error = ?error, "Request failed");
         }
+        problem
     } else if let Some(error) = r.find::<BodyDeserializeError>() {
-        Ok(warp::reply::with_status(
-            error.to_string(),
+        Problem::new(
+            "invalid-body",
+            "Invalid request body",
             StatusCode::UNPROCESSABLE_ENTITY,
-        ))
+            Some(error.to_string()),
+        )
     } else if let Some(error) = r.find::<CorsForbidden>() {
-        Ok(warp::reply::with_status(
-            error.to_string(),
+        Problem::new(
+            "cors-forbidden",
+            "CORS request forbidden",
             StatusCode::FORBIDDEN,
-        ))
+            Some(error.to_string()),
+        )
     } else {
-        Ok(warp::reply::with_status(
-            "Route not found".to_string(),
+        Problem::new(
+            "route-not-found",
+            "Route not found",
             StatusCode::NOT_FOUND,
-        ))
-    }
+            None,
+        )
+    };
+
+    Ok(problem.into_response())
 }
 
 #[cfg(test)]
@@ -93,10 +200,129 @@ mod tests {
         assert_eq!(response.status(), StatusCode::BAD_REQUEST);
     }
 
+    #[tokio::test]
+    async fn test_return_error_invalid_parameter() {
+        let rejection = warp::reject::custom(WarpError::from(CoreError::InvalidParameter(
+            "created_after".to_string(),
+        )));
+        let response = return_error(rejection).await.unwrap().into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_return_error_conflict() {
+        let rejection = warp::reject::custom(WarpError::from(CoreError::Conflict(
+            "question is at version 2".to_string(),
+        )));
+        let response = return_error(rejection).await.unwrap().into_response();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+
+    #[tokio::test]
+    async fn test_return_error_unavailable() {
+        let rejection = warp::reject::custom(WarpError::from(CoreError::Unavailable(
+            "timed out waiting for a connection".to_string(),
+        )));
+        let response = return_error(rejection).await.unwrap().into_response();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[tokio::test]
+    async fn test_return_error_unauthorized_and_forbidden() {
+        let rejection = warp::reject::custom(WarpError::from(CoreError::Unauthorized(
+            "missing token".to_string(),
+        )));
+        let response = return_error(rejection).await.unwrap().into_response();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(response.headers()[WWW_AUTHENTICATE], "Bearer");
+
+        let rejection = warp::reject::custom(WarpError::from(CoreError::Forbidden(
+            "admin only".to_string(),
+        )));
+        let response = return_error(rejection).await.unwrap().into_response();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_return_error_timeout_and_rate_limited() {
+        let rejection = warp::reject::custom(WarpError::from(CoreError::Timeout(
+            "gpt answer service".to_string(),
+        )));
+        let response = return_error(rejection).await.unwrap().into_response();
+        assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+
+        let rejection = warp::reject::custom(WarpError::from(CoreError::RateLimited(
+            "gpt answer service".to_string(),
+        )));
+        let response = return_error(rejection).await.unwrap().into_response();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    #[tokio::test]
+    async fn test_return_error_validation() {
+        let mut errors = ValidationErrors::default();
+        errors.add("title", "must not be blank");
+        let rejection = warp::reject::custom(WarpError::from(CoreError::Validation(errors)));
+        let response = return_error(rejection).await.unwrap().into_response();
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let body = warp::hyper::body::to_bytes(response.into_body())
+            .await
+            .unwrap();
+        let body: Problem = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body.kind, "urn:problem-type:validation-failed");
+        assert_eq!(body.errors.field("title"), ["must not be blank"]);
+    }
+
+    #[tokio::test]
+    async fn test_return_error_precondition_failed() {
+        let rejection = warp::reject::custom(WarpError::PreconditionFailed(
+            "question is at version 2".to_string(),
+        ));
+        let response = return_error(rejection).await.unwrap().into_response();
+        assert_eq!(response.status(), StatusCode::PRECONDITION_FAILED);
+    }
+
     #[tokio::test]
     async fn test_return_error_unknown_rejection() {
         let rejection = warp::reject::reject();
         let response = return_error(rejection).await.unwrap().into_response();
         assert_eq!(response.status(), StatusCode::NOT_FOUND);
     }
+
+    #[tokio::test]
+    async fn test_return_error_problem_json() {
+        let rejection = warp::reject::custom(WarpError::from(CoreError::Conflict(
+            "question is at version 2".to_string(),
+        )));
+        let response = return_error(rejection).await.unwrap().into_response();
+        assert_eq!(response.headers()[CONTENT_TYPE], PROBLEM_JSON);
+
+        let body = warp::hyper::body::to_bytes(response.into_body())
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["type"], "urn:problem-type:conflict");
+        assert_eq!(body["title"], "Conflict");
+        assert_eq!(body["status"], 409);
+        assert_eq!(body["detail"], "question is at version 2");
+        assert!(body["instance"].as_str().unwrap().starts_with("urn:uuid:"));
+        assert!(body.get("errors").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_return_error_hides_internal_details() {
+        let rejection = warp::reject::custom(WarpError::from(CoreError::InternalError(
+            anyhow::anyhow!("password authentication failed"),
+        )));
+        let response = return_error(rejection).await.unwrap().into_response();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+        let body = warp::hyper::body::to_bytes(response.into_body())
+            .await
+            .unwrap();
+        let body: Problem = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body.title, "Internal error");
+        assert_eq!(body.detail, None);
+    }
 }
@@ -1,3 +1,4 @@
+pub mod auth;
 pub mod controllers;
 pub mod errors;
 pub mod options;
@@ -1,7 +1,10 @@
-#[cfg_attr(debug_assertions, allow(dead_code, unused_imports))]
+#[cfg_attr(
+    debug_assertions,
+    allow(dead_code, unused_imports, clippy::single_component_path_imports)
+)]
 use openssl;
 #[rustfmt::skip]
-#[cfg_attr(debug_assertions, allow(dead_code, unused_imports))]
+#[cfg_attr(debug_assertions, allow(dead_code, unused_imports, clippy::single_component_path_imports))]
 use diesel;
 
 use std::net::{Ipv4Addr, SocketAddrV4};
@@ -14,17 +17,33 @@ use deadpool_diesel::{Manager, Runtime};
 use opentelemetry::global;
 use tokio::sync::oneshot;
 use tokio::sync::oneshot::Receiver;
-use tracing::info;
+use tracing::{error, info};
 
+use adapter::repositories::cached::question::CachedQuestionRepository;
 use adapter::repositories::grpc::gpt_answer_client::GptAnswerClient;
+use adapter::repositories::in_memory::answer::AnswerInMemoryRepository;
+use adapter::repositories::in_memory::cache::InMemoryCache;
+use adapter::repositories::in_memory::gpt_answer::GptAnswerInMemoryRepository;
 use adapter::repositories::in_memory::question::QuestionInMemoryRepository;
+use adapter::repositories::in_memory::question_revision::QuestionRevisionInMemoryRepository;
+use adapter::repositories::in_memory::tag::TagInMemoryRepository;
+use adapter::repositories::postgres::answer_db::AnswerDBRepository;
+use adapter::repositories::postgres::config::DBConfig;
+use adapter::repositories::postgres::migrator::Migrator;
 use adapter::repositories::postgres::question_db::QuestionDBRepository;
-use cli::options::Options;
+use adapter::repositories::postgres::question_revision_db::QuestionRevisionDBRepository;
+use adapter::repositories::postgres::tag_db::TagDBRepository;
+use adapter::repositories::redis::cache::RedisCache;
+use cli::options::{Cache, Options};
 use cli::router::Router;
 use common::kill_signals;
 use common::loggers::telemetry::init_telemetry;
 use common::options::parse_options;
+use rust_core::ports::answer::AnswerPort;
+use rust_core::ports::gpt_answer::GptAnswerPort;
 use rust_core::ports::question::QuestionPort;
+use rust_core::ports::question_revision::QuestionRevisionPort;
+use rust_core::ports::tag::TagPort;
 
 #[tokio::main]
 async fn main() {
@@ -42,9 +61,22 @@ async fn main() {
         }
     };
 
-    if let Some(Commands::Config) = args.command {
-        println!("{:#?}", options);
-        return;
+    match args.command {
+        Some(Commands::Config) => {
+            println!("{:#?}", options);
+            return;
+        }
+        Some(Commands::Migrate { command }) => {
+            match &options.db.pg {
+                Some(database_config) => migrate(database_config, command).await,
+                None => {
+                    eprintln!("Migrations need a postgres database, set db.pg");
+                    std::process::exit(1);
+                }
+            }
+            return;
+        }
+        None => {}
     }
 
     init_telemetry(
@@ -86,30 +118,181 @@ struct Args {
 enum Commands {
     /// Print config
     Config,
+    /// Manage database migrations
+    Migrate {
+        #[command(subcommand)]
+        command: MigrateCommands,
+    },
+}
+
+#[derive(Subcommand, Clone, Debug)]
+enum MigrateCommands {
+    /// Apply pending migrations
+    Up,
+    /// Revert the last applied migration
+    Down,
+    /// Print which migrations are applied
+    Status,
+    /// Revert the last applied migration and apply it again
+    Redo,
+}
+
+fn postgres_pool(database_config: &DBConfig) -> Pool {
+    let manager = Manager::new(database_config.url.clone(), Runtime::Tokio1);
+    Pool::builder(manager)
+        .max_size(database_config.max_size)
+        .build()
+        .unwrap()
+}
+
+async fn migrate(database_config: &DBConfig, command: MigrateCommands) {
+    let migrator = Migrator::new(postgres_pool(database_config));
+    let result = match command {
+        MigrateCommands::Up => migrator.up().await.map(|versions| {
+            if versions.is_empty() {
+                println!("No pending migrations");
+            }
+            for version in versions {
+                println!("Applied {}", version);
+            }
+        }),
+        MigrateCommands::Down => migrator
+            .down()
+            .await
+            .map(|version| println!("Reverted {}", version)),
+        MigrateCommands::Status => migrator.status().await.map(|migrations| {
+            for migration in migrations {
+                let state = if migration.applied {
+                    "applied"
+                } else {
+                    "pending"
+                };
+                println!("{} {}", migration.version, state);
+            }
+        }),
+        MigrateCommands::Redo => migrator
+            .redo()
+            .await
+            .map(|version| println!("Redid {}", version)),
+    };
+    if let Err(err) = result {
+        eprintln!("Failed to migrate: {}", err);
+        std::process::exit(1);
+    }
+}
+
+/// Ports backed by the same question storage.
+type QuestionPorts = (
+    Arc<dyn QuestionPort + Send + Sync>,
+    Arc<dyn QuestionRevisionPort + Send + Sync>,
+    Arc<dyn AnswerPort + Send + Sync>,
+    Arc<dyn TagPort + Send + Sync>,
+);
+
+fn in_memory_ports() -> QuestionPorts {
+    let question_repository = QuestionInMemoryRepository::new();
+    let question_revision_repository =
+        QuestionRevisionInMemoryRepository::new(&question_repository);
+    let answer_repository = AnswerInMemoryRepository::new(&question_repository);
+    let tag_repository = TagInMemoryRepository::new(&question_repository);
+    (
+        Arc::new(question_repository),
+        Arc::new(question_revision_repository),
+        Arc::new(answer_repository),
+        Arc::new(tag_repository),
+    )
+}
+
+/// Wraps `question_port` so that its reads go through the configured cache.
+async fn cached_question_port(
+    question_port: Arc<dyn QuestionPort + Send + Sync>,
+    cache: &Cache,
+) -> Arc<dyn QuestionPort + Send + Sync> {
+    match &cache.redis {
+        Some(redis) => {
+            info!("Caching questions in redis: {}:{}", redis.host, redis.port);
+            let redis_cache = RedisCache::new(&redis.host, redis.port)
+                .await
+                .expect("Failed to connect to redis");
+            Arc::new(CachedQuestionRepository::new(
+                question_port,
+                redis_cache,
+                &cache.ttl,
+            ))
+        }
+        None => {
+            info!("Caching questions in memory");
+            let in_memory_cache = InMemoryCache::new(cache.in_memory.clone().unwrap_or_default());
+            Arc::new(CachedQuestionRepository::new(
+                question_port,
+                in_memory_cache,
+                &cache.ttl,
+            ))
+        }
+    }
 }
 
 pub async fn serve(options: Options, rx: Receiver<()>) {
-    let question_port: Arc<dyn QuestionPort + Send + Sync> = if options.db.in_memory.is_some() {
-        info!("Using in-memory database");
-        Arc::new(QuestionInMemoryRepository::new())
-    } else if options.db.pg.is_some() {
-        let database_config = options.db.pg.clone().unwrap();
-        info!("Using postgres database: {}", database_config.url);
-        let manager = Manager::new(database_config.url, Runtime::Tokio1);
-        let pool = Pool::builder(manager)
-            .max_size(database_config.max_size)
-            .build()
-            .unwrap();
-        Arc::new(QuestionDBRepository::new(pool))
-    } else {
-        info!("No database specified, falling back to in-memory");
-        Arc::new(QuestionInMemoryRepository::new())
+    let (question_port, question_revision_port, answer_port, tag_port) =
+        if options.db.in_memory.is_some() {
+            info!("Using in-memory database");
+            in_memory_ports()
+        } else if options.db.pg.is_some() {
+            let database_config = options.db.pg.clone().unwrap();
+            info!("Using postgres database: {}", database_config.url);
+            let pool = postgres_pool(&database_config);
+            if database_config.run_migrations {
+                match Migrator::new(pool.clone()).up().await {
+                    Ok(versions) => info!("Applied {} pending migrations", versions.len()),
+                    Err(err) => {
+                        error!("Failed to run migrations: {}", err);
+                        global::shutdown_tracer_provider();
+                        std::process::exit(1);
+                    }
+                }
+            }
+            let question_ports: QuestionPorts = (
+                Arc::new(QuestionDBRepository::new(pool.clone())),
+                Arc::new(QuestionRevisionDBRepository::new(pool.clone())),
+                Arc::new(AnswerDBRepository::new(pool.clone())),
+                Arc::new(TagDBRepository::new(pool)),
+            );
+            question_ports
+        } else {
+            info!("No database specified, falling back to in-memory");
+            in_memory_ports()
+        };
+    let question_port = match &options.cache {
+        Some(cache) => cached_question_port(question_port, cache).await,
+        None => question_port,
     };
 
-    let gpt_answer_client =
-        Arc::new(GptAnswerClient::new(options.gpt_answer_service_url.to_string()).unwrap());
+    let gpt_answer_port: Arc<dyn GptAnswerPort + Send + Sync> = match &options.gpt_answer.in_memory
+    {
+        Some(in_memory) => {
+            info!("Using in-memory GPT answers");
+            match &in_memory.answer {
+                Some(answer) => Arc::new(GptAnswerInMemoryRepository::new(answer.clone())),
+                None => Arc::new(GptAnswerInMemoryRepository::default()),
+            }
+        }
+        None => {
+            info!(
+                "Using GPT answer service: {}",
+                options.gpt_answer_service_url
+            );
+            Arc::new(GptAnswerClient::new(options.gpt_answer_service_url.to_string()).unwrap())
+        }
+    };
 
-    let router = Router::new(question_port, gpt_answer_client);
+    let router = Router::new(
+        question_port,
+        question_revision_port,
+        answer_port,
+        tag_port,
+        gpt_answer_port,
+        options.auth.tokens.clone(),
+    );
     let routes = router.routes();
     let address = SocketAddrV4::new(
         Ipv4Addr::from_str(options.server.url.as_str()).unwrap(),
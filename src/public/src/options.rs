@@ -1,7 +1,13 @@
+use std::fmt;
+
 use serde::Deserialize;
 
+use adapter::repositories::cached::question::QuestionCacheConfig;
+use adapter::repositories::in_memory::cache::InMemoryCacheConfig;
 use adapter::repositories::postgres::config::DBConfig;
+use adapter::repositories::redis::config::RedisConfig;
 use common::options::{default_log, Log};
+use rust_core::entities::principal::Role;
 
 /// Configuration options for the application.
 ///
@@ -14,8 +20,16 @@ pub struct Options {
     pub server: Server,
     /// URL for the GPT Answer gRPC client.
     pub gpt_answer_service_url: String,
+    /// Specifies where answers to questions are generated.
+    #[serde(default)]
+    pub gpt_answer: GptAnswer,
     /// Specifies the configuration of database will be connected.
     pub db: Database,
+    /// Configuration for caching questions, disabled when absent.
+    pub cache: Option<Cache>,
+    /// Tokens callers authenticate with, none by default.
+    #[serde(default)]
+    pub auth: Auth,
     /// The endpoint for the exporter.
     pub exporter_endpoint: String,
     /// The name of the service.
@@ -25,6 +39,35 @@ pub struct Options {
     pub log: Log,
 }
 
+/// Represents authentication configuration.
+#[derive(Deserialize, Debug, Default)]
+pub struct Auth {
+    /// Bearer tokens accepted by the server, along with who presents them.
+    #[serde(default)]
+    pub tokens: Vec<ApiToken>,
+}
+
+/// Represents a bearer token and the caller presenting it.
+#[derive(Deserialize, Clone)]
+pub struct ApiToken {
+    /// Name of the caller, used in logs and error messages.
+    pub subject: String,
+    /// Secret sent in the `Authorization: Bearer` header.
+    pub token: String,
+    /// Role granted to the caller.
+    pub role: Role,
+}
+
+impl fmt::Debug for ApiToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ApiToken")
+            .field("subject", &self.subject)
+            .field("token", &"<redacted>")
+            .field("role", &self.role)
+            .finish()
+    }
+}
+
 /// Represents database configuration options.
 #[derive(Deserialize, Debug)]
 pub struct Database {
@@ -38,6 +81,36 @@ pub struct Database {
 #[derive(Deserialize, Debug)]
 pub struct InMemoryDatabase {}
 
+/// Represents question cache configuration.
+///
+/// Questions are cached in redis when `redis` is set, and in memory otherwise.
+#[derive(Deserialize, Debug)]
+pub struct Cache {
+    /// Time to live of cached questions, pages and counts.
+    #[serde(default)]
+    pub ttl: QuestionCacheConfig,
+    /// Configuration for caching in memory.
+    pub in_memory: Option<InMemoryCacheConfig>,
+    /// Configuration for caching in redis.
+    pub redis: Option<RedisConfig>,
+}
+
+/// Represents GPT answer configuration.
+///
+/// Answers come from the gRPC service at `gpt_answer_service_url` unless `in_memory` is set.
+#[derive(Deserialize, Debug, Default)]
+pub struct GptAnswer {
+    /// Configuration for answering without the gRPC service.
+    pub in_memory: Option<InMemoryGptAnswer>,
+}
+
+/// Represents in-memory GPT answer configuration.
+#[derive(Deserialize, Debug)]
+pub struct InMemoryGptAnswer {
+    /// Text given as the answer to every question, a fixed placeholder when absent.
+    pub answer: Option<String>,
+}
+
 /// Represents server configuration.
 #[derive(Debug, Deserialize, Clone)]
 pub struct Server {
@@ -3,52 +3,87 @@ use std::sync::Arc;
 use warp::http::Method;
 use warp::{Filter, Rejection, Reply};
 
-use adapter::repositories::grpc::gpt_answer_client::GptAnswerClient;
+use rust_core::ports::answer::AnswerPort;
+use rust_core::ports::gpt_answer::GptAnswerPort;
 use rust_core::ports::question::QuestionPort;
+use rust_core::ports::question_revision::QuestionRevisionPort;
+use rust_core::ports::tag::TagPort;
+use rust_core::services::answer::AnswerService;
+use rust_core::services::question::QuestionService;
+use rust_core::services::tag::TagService;
 
+use crate::auth::principal;
+use crate::controllers::answer::{add_question_answer, get_question_answer, get_question_answers};
 use crate::controllers::question::{
-    add_question, delete_question, get_question, get_question_answer, get_questions,
+    add_question, delete_question, get_question, get_questions, purge_question, restore_question,
     update_question,
 };
+use crate::controllers::question_revision::{
+    get_question_revision, get_question_revisions, revert_question,
+};
+use crate::controllers::tag::get_tags;
 use crate::errors::return_error;
+use crate::options::ApiToken;
 
 /// Router for handling HTTP requests related to questions.
 pub struct Router {
-    question_port: Arc<dyn QuestionPort + Send + Sync + 'static>,
-    gpt_answer_client: Arc<GptAnswerClient>,
+    question_service: Arc<QuestionService>,
+    answer_service: Arc<AnswerService>,
+    tag_service: Arc<TagService>,
+    tokens: Arc<Vec<ApiToken>>,
 }
 
 impl Router {
-    /// Creates a new Router instance with the specified QuestionPort.
+    /// Creates a new Router instance with the specified QuestionPort, QuestionRevisionPort,
+    /// AnswerPort, TagPort and GptAnswerPort, wiring them into the question, answer and tag
+    /// services. Callers authenticate with one of `tokens`.
     pub fn new(
         question_port: Arc<dyn QuestionPort + Send + Sync + 'static>,
-        gpt_answer_client: Arc<GptAnswerClient>,
+        question_revision_port: Arc<dyn QuestionRevisionPort + Send + Sync + 'static>,
+        answer_port: Arc<dyn AnswerPort + Send + Sync + 'static>,
+        tag_port: Arc<dyn TagPort + Send + Sync + 'static>,
+        gpt_answer_port: Arc<dyn GptAnswerPort + Send + Sync + 'static>,
+        tokens: Vec<ApiToken>,
     ) -> Self {
         Router {
-            question_port: question_port.clone(),
-            gpt_answer_client,
+            question_service: Arc::new(QuestionService::new(
+                question_port.clone(),
+                question_revision_port,
+            )),
+            answer_service: Arc::new(AnswerService::new(
+                question_port,
+                answer_port,
+                gpt_answer_port,
+            )),
+            tag_service: Arc::new(TagService::new(tag_port)),
+            tokens: Arc::new(tokens),
         }
     }
 
     /// Configures and returns the Warp filter for handling HTTP requests.
     pub fn routes(self) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
-        let store_filter = warp::any().map(move || self.question_port.clone());
+        let question_service_filter = warp::any().map(move || self.question_service.clone());
+        let answer_service_filter = warp::any().map(move || self.answer_service.clone());
+        let tag_service_filter = warp::any().map(move || self.tag_service.clone());
+        let principal_filter = principal(self.tokens);
 
         let cors = warp::cors()
             .allow_any_origin()
-            .allow_header("content-type")
+            .allow_headers(vec!["authorization", "content-type", "if-match"])
+            .expose_headers(vec!["etag", "location", "link"])
             .allow_methods(&[Method::PUT, Method::DELETE, Method::GET, Method::POST]);
 
         let get_questions = warp::get()
             .and(warp::path("questions"))
             .and(warp::path::end())
-            .and(store_filter.clone())
+            .and(question_service_filter.clone())
+            .and(warp::path::full())
             .and(warp::query())
             .and_then(get_questions);
 
         let get_question = warp::get()
             .and(warp::path("questions"))
-            .and(store_filter.clone())
+            .and(question_service_filter.clone())
             .and(warp::path::param::<String>())
             .and(warp::path::end())
             .and_then(get_question);
@@ -56,41 +91,124 @@ impl Router {
         let add_question = warp::post()
             .and(warp::path("questions"))
             .and(warp::path::end())
-            .and(store_filter.clone())
+            .and(question_service_filter.clone())
+            .and(warp::path::full())
             .and(warp::body::json())
             .and_then(add_question);
 
         let update_question = warp::put()
             .and(warp::path("questions"))
-            .and(store_filter.clone())
+            .and(question_service_filter.clone())
             .and(warp::path::param::<String>())
             .and(warp::path::end())
+            .and(warp::header::optional::<String>("if-match"))
             .and(warp::body::json())
             .and_then(update_question);
 
         let delete_question = warp::delete()
             .and(warp::path("questions"))
-            .and(store_filter.clone())
+            .and(question_service_filter.clone())
             .and(warp::path::param::<String>())
             .and(warp::path::end())
+            .and(warp::header::optional::<String>("if-match"))
             .and_then(delete_question);
 
+        let get_question_revisions = warp::get()
+            .and(warp::path("questions"))
+            .and(question_service_filter.clone())
+            .and(warp::path::param::<String>())
+            .and(warp::path("revisions"))
+            .and(warp::path::end())
+            .and_then(get_question_revisions);
+
+        let get_question_revision = warp::get()
+            .and(warp::path("questions"))
+            .and(question_service_filter.clone())
+            .and(warp::path::param::<String>())
+            .and(warp::path("revisions"))
+            .and(warp::path::param::<i32>())
+            .and(warp::path::end())
+            .and_then(get_question_revision);
+
+        let revert_question = warp::post()
+            .and(warp::path("questions"))
+            .and(question_service_filter.clone())
+            .and(warp::path::param::<String>())
+            .and(warp::path("revisions"))
+            .and(warp::path::param::<i32>())
+            .and(warp::path("revert"))
+            .and(warp::path::end())
+            .and(warp::header::optional::<String>("if-match"))
+            .and_then(revert_question);
+
+        let restore_question = warp::post()
+            .and(warp::path("admin"))
+            .and(warp::path("questions"))
+            .and(question_service_filter.clone())
+            .and(warp::path::param::<String>())
+            .and(warp::path("restore"))
+            .and(warp::path::end())
+            .and(principal_filter.clone())
+            .and_then(restore_question);
+
+        let purge_question = warp::delete()
+            .and(warp::path("admin"))
+            .and(warp::path("questions"))
+            .and(question_service_filter.clone())
+            .and(warp::path::param::<String>())
+            .and(warp::path::end())
+            .and(principal_filter)
+            .and_then(purge_question);
+
+        let get_question_answers = warp::get()
+            .and(warp::path("questions"))
+            .and(answer_service_filter.clone())
+            .and(warp::path::param::<String>())
+            .and(warp::path("answers"))
+            .and(warp::path::end())
+            .and_then(get_question_answers);
+
         let get_question_answer = warp::get()
             .and(warp::path("questions"))
-            .and(store_filter.clone())
-            .and(warp::any().map(move || self.gpt_answer_client.clone()))
+            .and(answer_service_filter.clone())
+            .and(warp::path::param::<String>())
+            .and(warp::path("answers"))
             .and(warp::path::param::<String>())
-            .and(warp::path("answer"))
+            .and(warp::path::end())
             .and_then(get_question_answer);
 
+        let add_question_answer = warp::post()
+            .and(warp::path("questions"))
+            .and(answer_service_filter.clone())
+            .and(warp::path::param::<String>())
+            .and(warp::path("answers"))
+            .and(warp::path::end())
+            .and(warp::path::full())
+            .and(warp::body::json())
+            .and_then(add_question_answer);
+
+        let get_tags = warp::get()
+            .and(warp::path("tags"))
+            .and(warp::path::end())
+            .and(tag_service_filter)
+            .and_then(get_tags);
+
         get_questions
-            .with(cors)
             .or(get_question)
             .or(delete_question)
             .or(update_question)
             .or(add_question)
+            .or(get_question_answers)
             .or(get_question_answer)
-            .with(warp::trace::request())
+            .or(add_question_answer)
+            .or(get_question_revisions)
+            .or(get_question_revision)
+            .or(revert_question)
+            .or(restore_question)
+            .or(purge_question)
+            .or(get_tags)
+            .with(cors)
             .recover(return_error)
+            .with(warp::trace::request())
     }
 }
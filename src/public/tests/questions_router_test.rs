@@ -1,70 +1,263 @@
 mod tests {
-    use std::{str::FromStr, sync::Arc};
+    use std::sync::Arc;
 
     use deadpool_diesel::{
         postgres::{Pool, Runtime},
         Manager,
     };
     use diesel_migrations::MigrationHarness;
-    use rand::Rng;
     use testcontainers_modules::postgres;
     use testcontainers_modules::testcontainers::runners::AsyncRunner;
     use warp::http::StatusCode;
     use warp::test::request;
 
     use adapter::repositories::{
-        grpc::gpt_answer_client::GptAnswerClient,
-        in_memory::question::QuestionInMemoryRepository,
-        postgres::question_db::{QuestionDBRepository, MIGRATIONS},
+        in_memory::{
+            answer::AnswerInMemoryRepository,
+            gpt_answer::{GptAnswerInMemoryRepository, DEFAULT_ANSWER},
+            question::QuestionInMemoryRepository,
+            question_revision::QuestionRevisionInMemoryRepository,
+            tag::TagInMemoryRepository,
+        },
+        postgres::{
+            answer_db::AnswerDBRepository,
+            question_db::{QuestionDBRepository, MIGRATIONS},
+            question_revision_db::QuestionRevisionDBRepository,
+            tag_db::TagDBRepository,
+        },
     };
+    use cli::controllers::answer::AddAnswerRequest;
+    use cli::controllers::question::QuestionPage;
+    use cli::controllers::question_revision::QuestionRevisionDetail;
+    use cli::errors::{Problem, PROBLEM_JSON};
+    use cli::options::ApiToken;
     use cli::router::Router;
     use rust_core::{
-        entities::question::{QuestionEntity, QuestionId},
-        ports::question::QuestionPort,
+        entities::{
+            answer::AnswerEntity,
+            principal::Role,
+            question::{CreateQuestionEntity, QuestionEntity},
+            question_revision::QuestionRevisionEntity,
+            tag::TagEntity,
+        },
+        ports::{
+            answer::AnswerPort, question::QuestionPort, question_revision::QuestionRevisionPort,
+            tag::TagPort,
+        },
+        services::answer::GPT_ANSWER_SOURCE,
     };
 
+    const ADMIN_TOKEN: &str = "admin-token";
+    const MEMBER_TOKEN: &str = "member-token";
+
     struct DatabaseConfig {
         url: String,
         max_size: usize,
     }
 
-    async fn question_router_test<T>(question_port: Arc<T>)
-    where
+    async fn question_router_test<T, R, A, G>(
+        question_port: Arc<T>,
+        question_revision_port: Arc<R>,
+        answer_port: Arc<A>,
+        tag_port: Arc<G>,
+    ) where
         T: QuestionPort + Send + Sync + 'static,
+        R: QuestionRevisionPort + Send + Sync + 'static,
+        A: AnswerPort + Send + Sync + 'static,
+        G: TagPort + Send + Sync + 'static,
     {
-        let gpt_answer_service_url = "grpc://0.0.0.0:50051".to_string();
-
-        let gpt_answer_client: Arc<
-            adapter::repositories::grpc::gpt_answer_client::GptAnswerClient,
-        > = Arc::new(GptAnswerClient::new(gpt_answer_service_url.to_string()).unwrap());
-
-        let router = Router::new(question_port, gpt_answer_client);
+        let gpt_answer_port = Arc::new(GptAnswerInMemoryRepository::default());
+
+        let router = Router::new(
+            question_port,
+            question_revision_port,
+            answer_port,
+            tag_port,
+            gpt_answer_port,
+            vec![
+                ApiToken {
+                    subject: "ops".to_string(),
+                    token: ADMIN_TOKEN.to_string(),
+                    role: Role::Admin,
+                },
+                ApiToken {
+                    subject: "reader".to_string(),
+                    token: MEMBER_TOKEN.to_string(),
+                    role: Role::Member,
+                },
+            ],
+        );
         let routers = router.routes();
 
-        let raw_question_id: String = rand::thread_rng().gen_range(1..=1000).to_string();
-        let question_id = QuestionId::from_str(&raw_question_id.clone()).unwrap();
-
-        let question = QuestionEntity {
-            id: question_id.clone(),
+        let new_question = CreateQuestionEntity {
             title: "How to write integration tests in Rust?".to_string(),
             content: "I'm trying to write integration tests for a Warp router, but I'm not sure how to do it. Can someone help me?".to_string(),
             tags: Some(vec!["rust".to_string(), "warp".to_string()]),
         };
 
+        // Test POST /questions with invalid fields
+        let invalid_resp = request()
+            .method("POST")
+            .path("/questions")
+            .json(&CreateQuestionEntity {
+                title: "   ".to_string(),
+                content: "Why?".to_string(),
+                tags: Some(vec!["rust lang".to_string()]),
+            })
+            .reply(&routers)
+            .await;
+
+        assert_eq!(
+            invalid_resp.status(),
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "An invalid question should be rejected"
+        );
+        assert_eq!(invalid_resp.headers()["content-type"], PROBLEM_JSON);
+        let invalid_body: Problem = serde_json::from_slice(invalid_resp.body())
+            .expect("Failed to deserialize response body");
+        assert_eq!(invalid_body.errors.field("title"), ["must not be blank"]);
+        assert_eq!(invalid_body.errors.field("tags").len(), 1);
+        assert!(invalid_body.errors.field("content").is_empty());
+
+        // Test an unmatched route
+        let unmatched_resp = request()
+            .method("GET")
+            .path("/answers")
+            .reply(&routers)
+            .await;
+
+        assert_eq!(unmatched_resp.status(), StatusCode::NOT_FOUND);
+        assert_eq!(unmatched_resp.headers()["content-type"], PROBLEM_JSON);
+        let unmatched_body: Problem = serde_json::from_slice(unmatched_resp.body())
+            .expect("Failed to deserialize response body");
+        assert_eq!(unmatched_body.kind, "urn:problem-type:route-not-found");
+
         // Test POST /questions to create a new question
         let post_resp = request()
             .method("POST")
             .path("/questions")
-            .json(&question)
+            .json(&new_question)
             .reply(&routers)
             .await;
 
         assert_eq!(
             post_resp.status(),
-            StatusCode::OK,
+            StatusCode::CREATED,
             "Failed to create a new question"
         );
 
+        let question: QuestionEntity =
+            serde_json::from_slice(post_resp.body()).expect("Failed to deserialize response body");
+        let question_id = question.id.clone();
+        let raw_question_id = question_id.to_string();
+
+        assert_eq!(
+            question,
+            QuestionEntity {
+                created_on: question.created_on,
+                updated_on: question.updated_on,
+                ..new_question.into_entity(question_id.clone())
+            },
+            "The stored question is not as expected"
+        );
+        assert_eq!(
+            question.created_on, question.updated_on,
+            "A new question has not been updated yet"
+        );
+        assert_eq!(
+            post_resp.headers().get("location").unwrap(),
+            format!("/questions/{}", raw_question_id).as_str(),
+            "The location does not point at the created question"
+        );
+
+        // Test GET /questions to list the created question
+        let list_resp = request()
+            .method("GET")
+            .path("/questions?start=0&end=1")
+            .reply(&routers)
+            .await;
+
+        assert_eq!(
+            list_resp.status(),
+            StatusCode::OK,
+            "Failed to list the questions"
+        );
+
+        let question_page: QuestionPage =
+            serde_json::from_slice(list_resp.body()).expect("Failed to deserialize response body");
+
+        assert_eq!(question_page.items, vec![question.clone()]);
+        assert_eq!(question_page.total, 1);
+        assert_eq!(question_page.start, Some(0));
+        assert_eq!(question_page.end, Some(1));
+        assert!(
+            list_resp.headers().get("link").is_none(),
+            "A single page has no neighbouring pages"
+        );
+
+        // Test GET /questions?q= to search the questions
+        let search_resp = request()
+            .method("GET")
+            .path("/questions?q=warp%20router")
+            .reply(&routers)
+            .await;
+
+        assert_eq!(
+            search_resp.status(),
+            StatusCode::OK,
+            "Failed to search the questions"
+        );
+        let search_page: QuestionPage = serde_json::from_slice(search_resp.body())
+            .expect("Failed to deserialize response body");
+        assert_eq!(search_page.items, vec![question.clone()]);
+        assert_eq!(search_page.total, 1);
+
+        let search_resp = request()
+            .method("GET")
+            .path("/questions?q=kubernetes")
+            .reply(&routers)
+            .await;
+        let search_page: QuestionPage = serde_json::from_slice(search_resp.body())
+            .expect("Failed to deserialize response body");
+        assert!(search_page.items.is_empty());
+        assert_eq!(search_page.total, 0);
+
+        let sorted_search_resp = request()
+            .method("GET")
+            .path("/questions?q=warp&sort=title")
+            .reply(&routers)
+            .await;
+
+        assert_eq!(
+            sorted_search_resp.status(),
+            StatusCode::BAD_REQUEST,
+            "Search results are ranked, not sorted"
+        );
+
+        // Test GET /tags to count the tags in use
+        let tags_resp = request().method("GET").path("/tags").reply(&routers).await;
+
+        assert_eq!(
+            tags_resp.status(),
+            StatusCode::OK,
+            "Failed to list the tags"
+        );
+        let tags: Vec<TagEntity> =
+            serde_json::from_slice(tags_resp.body()).expect("Failed to deserialize response body");
+        assert_eq!(
+            tags,
+            vec![
+                TagEntity {
+                    name: "rust".to_string(),
+                    count: 1
+                },
+                TagEntity {
+                    name: "warp".to_string(),
+                    count: 1
+                }
+            ]
+        );
+
         // Test GET /questions to get the created question
         let get_resp = request()
             .method("GET")
@@ -77,6 +270,11 @@ mod tests {
             StatusCode::OK,
             "Failed to get the created question"
         );
+        assert_eq!(
+            get_resp.headers().get("etag").unwrap(),
+            "\"1\"",
+            "The entity tag does not carry the question version"
+        );
 
         // Deserialize the response body into a Value
         let get_question: QuestionEntity =
@@ -87,6 +285,74 @@ mod tests {
             "The created question is not as expected"
         );
 
+        // Test CORS on a route other than GET /questions
+        let preflight_resp = request()
+            .method("OPTIONS")
+            .path(format!("/questions/{}", raw_question_id).as_str())
+            .header("origin", "https://example.com")
+            .header("access-control-request-method", "PUT")
+            .header("access-control-request-headers", "if-match")
+            .reply(&routers)
+            .await;
+
+        assert_eq!(
+            preflight_resp.status(),
+            StatusCode::OK,
+            "Failed to answer the CORS preflight"
+        );
+
+        let cors_resp = request()
+            .method("GET")
+            .path(format!("/questions/{}", raw_question_id).as_str())
+            .header("origin", "https://example.com")
+            .reply(&routers)
+            .await;
+
+        assert_eq!(
+            cors_resp.headers()["access-control-allow-origin"],
+            "https://example.com"
+        );
+        let exposed = cors_resp.headers()["access-control-expose-headers"]
+            .to_str()
+            .unwrap();
+        for header in ["etag", "location", "link"] {
+            assert!(
+                exposed.contains(header),
+                "{} is not exposed to browsers",
+                header
+            );
+        }
+
+        // Test GET /questions with an id that is not a UUID
+        let get_invalid_resp = request()
+            .method("GET")
+            .path("/questions/1")
+            .reply(&routers)
+            .await;
+
+        assert_eq!(
+            get_invalid_resp.status(),
+            StatusCode::BAD_REQUEST,
+            "A malformed id should be rejected"
+        );
+
+        // Test PUT /questions with an overlong title
+        let invalid_put_resp = request()
+            .method("PUT")
+            .path(format!("/questions/{}", raw_question_id).as_str())
+            .json(&QuestionEntity {
+                title: "x".repeat(256),
+                ..question.clone()
+            })
+            .reply(&routers)
+            .await;
+
+        assert_eq!(
+            invalid_put_resp.status(),
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "An overlong title should be rejected before it reaches the database"
+        );
+
         // Test PUT /questions to update the created question
         let updated_question = QuestionEntity {
             id: question_id.clone(),
@@ -97,6 +363,10 @@ mod tests {
                 "warp".to_string(),
                 "testing".to_string(),
             ]),
+            version: question.version,
+            created_on: question.created_on,
+            updated_on: question.updated_on,
+            deleted_on: None,
         };
 
         let put_resp = request()
@@ -111,6 +381,86 @@ mod tests {
             StatusCode::OK,
             "Failed to update the created question"
         );
+        assert_eq!(
+            put_resp.headers().get("etag").unwrap(),
+            "\"2\"",
+            "The update did not bump the question version"
+        );
+
+        // Test PUT /questions with a stale version in the body
+        let stale_put_resp = request()
+            .method("PUT")
+            .path(format!("/questions/{}", raw_question_id).as_str())
+            .json(&updated_question)
+            .reply(&routers)
+            .await;
+
+        assert_eq!(
+            stale_put_resp.status(),
+            StatusCode::CONFLICT,
+            "A stale update should conflict"
+        );
+
+        // Test PUT and DELETE /questions with a stale If-Match
+        let stale_put_resp = request()
+            .method("PUT")
+            .path(format!("/questions/{}", raw_question_id).as_str())
+            .header("if-match", "\"1\"")
+            .json(&updated_question)
+            .reply(&routers)
+            .await;
+
+        assert_eq!(
+            stale_put_resp.status(),
+            StatusCode::PRECONDITION_FAILED,
+            "A stale If-Match on update should fail the precondition"
+        );
+
+        let stale_delete_resp = request()
+            .method("DELETE")
+            .path(format!("/questions/{}", raw_question_id).as_str())
+            .header("if-match", "\"1\"")
+            .reply(&routers)
+            .await;
+
+        assert_eq!(
+            stale_delete_resp.status(),
+            StatusCode::PRECONDITION_FAILED,
+            "A stale If-Match on delete should fail the precondition"
+        );
+
+        // Test PUT /questions with an If-Match listing several versions
+        let listed_put_resp = request()
+            .method("PUT")
+            .path(format!("/questions/{}", raw_question_id).as_str())
+            .header("if-match", "\"1\", \"3\"")
+            .json(&updated_question)
+            .reply(&routers)
+            .await;
+
+        assert_eq!(
+            listed_put_resp.status(),
+            StatusCode::PRECONDITION_FAILED,
+            "An If-Match listing only stale versions should fail the precondition"
+        );
+
+        let listed_delete_resp = request()
+            .method("DELETE")
+            .path(format!("/questions/{}", raw_question_id).as_str())
+            .header("if-match", "W/\"2\", \"7\"")
+            .reply(&routers)
+            .await;
+
+        assert_eq!(
+            listed_delete_resp.status(),
+            StatusCode::PRECONDITION_FAILED,
+            "A weak tag in an If-Match list should never match"
+        );
+
+        let updated_question = QuestionEntity {
+            version: 2,
+            ..updated_question
+        };
 
         // Test GET /questions to get the updated question
         let get_updated_resp = request()
@@ -130,14 +480,23 @@ mod tests {
             .expect("Failed to deserialize response body");
 
         assert_eq!(
-            get_updated_question, updated_question,
+            get_updated_question,
+            QuestionEntity {
+                updated_on: get_updated_question.updated_on,
+                ..updated_question.clone()
+            },
             "The updated question is not as expected"
         );
+        assert!(
+            get_updated_question.updated_on > question.updated_on,
+            "The update time was not maintained"
+        );
 
         // Test DELETE /questions to delete the created question
         let delete_resp = request()
             .method("DELETE")
             .path(format!("/questions/{}", raw_question_id).as_str())
+            .header("if-match", "\"1\", \"2\"")
             .reply(&routers)
             .await;
 
@@ -160,6 +519,356 @@ mod tests {
             "The deleted question still exists"
         );
 
+        // Test POST /admin/questions/{id}/restore to bring the deleted question back
+        let anonymous_restore_resp = request()
+            .method("POST")
+            .path(format!("/admin/questions/{}/restore", raw_question_id).as_str())
+            .reply(&routers)
+            .await;
+
+        assert_eq!(
+            anonymous_restore_resp.status(),
+            StatusCode::UNAUTHORIZED,
+            "Restoring a question needs a token"
+        );
+        assert_eq!(
+            anonymous_restore_resp.headers()["www-authenticate"],
+            "Bearer"
+        );
+
+        let unknown_token_restore_resp = request()
+            .method("POST")
+            .path(format!("/admin/questions/{}/restore", raw_question_id).as_str())
+            .header("authorization", "Bearer unknown-token")
+            .reply(&routers)
+            .await;
+
+        assert_eq!(
+            unknown_token_restore_resp.status(),
+            StatusCode::UNAUTHORIZED,
+            "An unknown token should be rejected"
+        );
+
+        let member_restore_resp = request()
+            .method("POST")
+            .path(format!("/admin/questions/{}/restore", raw_question_id).as_str())
+            .header("authorization", format!("Bearer {}", MEMBER_TOKEN))
+            .reply(&routers)
+            .await;
+
+        assert_eq!(
+            member_restore_resp.status(),
+            StatusCode::FORBIDDEN,
+            "Only admins may restore questions"
+        );
+
+        let restore_resp = request()
+            .method("POST")
+            .path(format!("/admin/questions/{}/restore", raw_question_id).as_str())
+            .header("authorization", format!("Bearer {}", ADMIN_TOKEN))
+            .reply(&routers)
+            .await;
+
+        assert_eq!(
+            restore_resp.status(),
+            StatusCode::OK,
+            "Failed to restore the deleted question"
+        );
+
+        let restored_question: QuestionEntity = serde_json::from_slice(restore_resp.body())
+            .expect("Failed to deserialize response body");
+
+        assert_eq!(restored_question.deleted_on, None);
+        assert_eq!(restored_question.title, updated_question.title);
+
+        let get_restored_resp = request()
+            .method("GET")
+            .path(format!("/questions/{}", raw_question_id).as_str())
+            .reply(&routers)
+            .await;
+
+        assert_eq!(
+            get_restored_resp.status(),
+            StatusCode::OK,
+            "The restored question is still hidden"
+        );
+
+        // Test GET /questions/{id}/revisions to list the history of the question
+        let revisions_resp = request()
+            .method("GET")
+            .path(format!("/questions/{}/revisions", raw_question_id).as_str())
+            .reply(&routers)
+            .await;
+
+        assert_eq!(
+            revisions_resp.status(),
+            StatusCode::OK,
+            "Failed to list the revisions"
+        );
+
+        let revisions: Vec<QuestionRevisionEntity> = serde_json::from_slice(revisions_resp.body())
+            .expect("Failed to deserialize response body");
+
+        assert_eq!(
+            revisions
+                .iter()
+                .map(|revision| (revision.revision, revision.title.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (1, question.title.as_str()),
+                (2, updated_question.title.as_str())
+            ],
+            "Every write should leave a revision"
+        );
+
+        // Test GET /questions/{id}/revisions/{n} to see what a revision changed
+        let revision_resp = request()
+            .method("GET")
+            .path(format!("/questions/{}/revisions/2", raw_question_id).as_str())
+            .reply(&routers)
+            .await;
+
+        assert_eq!(
+            revision_resp.status(),
+            StatusCode::OK,
+            "Failed to get the revision"
+        );
+
+        let revision: QuestionRevisionDetail = serde_json::from_slice(revision_resp.body())
+            .expect("Failed to deserialize response body");
+
+        assert_eq!(revision.revision, revisions[1]);
+        assert_eq!(
+            revision
+                .changes
+                .iter()
+                .map(|change| change.field.as_str())
+                .collect::<Vec<_>>(),
+            vec!["title", "content", "tags"]
+        );
+
+        let missing_revision_resp = request()
+            .method("GET")
+            .path(format!("/questions/{}/revisions/3", raw_question_id).as_str())
+            .reply(&routers)
+            .await;
+
+        assert_eq!(
+            missing_revision_resp.status(),
+            StatusCode::NOT_FOUND,
+            "Soft deletes and restores leave no revision"
+        );
+
+        // Test POST /questions/{id}/revisions/{n}/revert to go back to the first revision
+        let stale_revert_resp = request()
+            .method("POST")
+            .path(format!("/questions/{}/revisions/1/revert", raw_question_id).as_str())
+            .header("if-match", "\"2\"")
+            .reply(&routers)
+            .await;
+
+        assert_eq!(
+            stale_revert_resp.status(),
+            StatusCode::PRECONDITION_FAILED,
+            "A stale If-Match on revert should fail the precondition"
+        );
+
+        let revert_resp = request()
+            .method("POST")
+            .path(format!("/questions/{}/revisions/1/revert", raw_question_id).as_str())
+            .header(
+                "if-match",
+                format!("\"{}\"", restored_question.version).as_str(),
+            )
+            .reply(&routers)
+            .await;
+
+        assert_eq!(
+            revert_resp.status(),
+            StatusCode::OK,
+            "Failed to revert the question"
+        );
+
+        let reverted_question: QuestionEntity = serde_json::from_slice(revert_resp.body())
+            .expect("Failed to deserialize response body");
+
+        assert_eq!(reverted_question.title, question.title);
+        assert_eq!(reverted_question.content, question.content);
+        assert_eq!(reverted_question.tags, question.tags);
+        assert_eq!(reverted_question.version, restored_question.version + 1);
+
+        // Test POST /questions/{id}/answers with a human answer
+        let answer_resp = request()
+            .method("POST")
+            .path(format!("/questions/{}/answers", raw_question_id).as_str())
+            .json(&AddAnswerRequest {
+                body: Some("Use warp::test::request against the routes.".to_string()),
+            })
+            .reply(&routers)
+            .await;
+
+        assert_eq!(
+            answer_resp.status(),
+            StatusCode::CREATED,
+            "Failed to add an answer"
+        );
+        let answer: AnswerEntity = serde_json::from_slice(answer_resp.body())
+            .expect("Failed to deserialize response body");
+        assert_eq!(answer.question_id.to_string(), raw_question_id);
+        assert_eq!(answer.source_model, None);
+        let answer_location = answer_resp.headers()["location"]
+            .to_str()
+            .unwrap()
+            .to_string();
+        assert_eq!(
+            answer_location,
+            format!("/questions/{}/answers/{}", raw_question_id, answer.id)
+        );
+
+        let answers_resp = request()
+            .method("GET")
+            .path(format!("/questions/{}/answers", raw_question_id).as_str())
+            .reply(&routers)
+            .await;
+
+        assert_eq!(answers_resp.status(), StatusCode::OK);
+        let answers: Vec<AnswerEntity> = serde_json::from_slice(answers_resp.body())
+            .expect("Failed to deserialize response body");
+        assert_eq!(answers, vec![answer.clone()]);
+
+        // Test POST /questions/{id}/answers without a body to generate an answer
+        let generated_resp = request()
+            .method("POST")
+            .path(format!("/questions/{}/answers", raw_question_id).as_str())
+            .json(&AddAnswerRequest::default())
+            .reply(&routers)
+            .await;
+
+        assert_eq!(
+            generated_resp.status(),
+            StatusCode::CREATED,
+            "Failed to generate an answer"
+        );
+        let generated_answer: AnswerEntity = serde_json::from_slice(generated_resp.body())
+            .expect("Failed to deserialize response body");
+        assert_eq!(generated_answer.body, DEFAULT_ANSWER);
+        assert_eq!(
+            generated_answer.source_model.as_deref(),
+            Some(GPT_ANSWER_SOURCE)
+        );
+
+        let answer_get_resp = request()
+            .method("GET")
+            .path(answer_location.as_str())
+            .reply(&routers)
+            .await;
+
+        assert_eq!(answer_get_resp.status(), StatusCode::OK);
+        let fetched_answer: AnswerEntity = serde_json::from_slice(answer_get_resp.body())
+            .expect("Failed to deserialize response body");
+        assert_eq!(fetched_answer, answer);
+
+        let invalid_answer_resp = request()
+            .method("GET")
+            .path(format!("/questions/{}/answers/42", raw_question_id).as_str())
+            .reply(&routers)
+            .await;
+
+        assert_eq!(
+            invalid_answer_resp.status(),
+            StatusCode::BAD_REQUEST,
+            "An invalid answer id should be rejected"
+        );
+
+        // Test DELETE /admin/questions/{id} to purge the question
+        let anonymous_purge_resp = request()
+            .method("DELETE")
+            .path(format!("/admin/questions/{}", raw_question_id).as_str())
+            .reply(&routers)
+            .await;
+
+        assert_eq!(
+            anonymous_purge_resp.status(),
+            StatusCode::UNAUTHORIZED,
+            "Purging a question needs a token"
+        );
+
+        let member_purge_resp = request()
+            .method("DELETE")
+            .path(format!("/admin/questions/{}", raw_question_id).as_str())
+            .header("authorization", format!("Bearer {}", MEMBER_TOKEN))
+            .reply(&routers)
+            .await;
+
+        assert_eq!(
+            member_purge_resp.status(),
+            StatusCode::FORBIDDEN,
+            "Only admins may purge questions"
+        );
+
+        let purge_live_resp = request()
+            .method("DELETE")
+            .path(format!("/admin/questions/{}", raw_question_id).as_str())
+            .header("authorization", format!("Bearer {}", ADMIN_TOKEN))
+            .reply(&routers)
+            .await;
+
+        assert_eq!(
+            purge_live_resp.status(),
+            StatusCode::CONFLICT,
+            "Only deleted questions can be purged"
+        );
+
+        let delete_resp = request()
+            .method("DELETE")
+            .path(format!("/questions/{}", raw_question_id).as_str())
+            .reply(&routers)
+            .await;
+
+        assert_eq!(
+            delete_resp.status(),
+            StatusCode::OK,
+            "Failed to delete the restored question"
+        );
+
+        let purge_resp = request()
+            .method("DELETE")
+            .path(format!("/admin/questions/{}", raw_question_id).as_str())
+            .header("authorization", format!("Bearer {}", ADMIN_TOKEN))
+            .reply(&routers)
+            .await;
+
+        assert_eq!(
+            purge_resp.status(),
+            StatusCode::OK,
+            "Failed to purge the deleted question"
+        );
+
+        let restore_purged_resp = request()
+            .method("POST")
+            .path(format!("/admin/questions/{}/restore", raw_question_id).as_str())
+            .header("authorization", format!("Bearer {}", ADMIN_TOKEN))
+            .reply(&routers)
+            .await;
+
+        assert_eq!(
+            restore_purged_resp.status(),
+            StatusCode::NOT_FOUND,
+            "A purged question cannot be restored"
+        );
+
+        let purged_answer_resp = request()
+            .method("GET")
+            .path(answer_location.as_str())
+            .reply(&routers)
+            .await;
+
+        assert_eq!(
+            purged_answer_resp.status(),
+            StatusCode::NOT_FOUND,
+            "Answers go away with their purged question"
+        );
+
         // Test UPDATE non-existence question
         let put_resp = request()
             .method("PUT")
@@ -211,13 +920,20 @@ mod tests {
 
         // Create a Router instance with the postgres db question port
         let question_port = Arc::new(QuestionDBRepository::new(db_pool.clone()));
-        question_router_test(question_port).await;
+        let question_revision_port = Arc::new(QuestionRevisionDBRepository::new(db_pool.clone()));
+        let answer_port = Arc::new(AnswerDBRepository::new(db_pool.clone()));
+        let tag_port = Arc::new(TagDBRepository::new(db_pool.clone()));
+        question_router_test(question_port, question_revision_port, answer_port, tag_port).await;
     }
 
     #[tokio::test]
     async fn questions_router_in_memory_test() {
         // Create a Router instance with the in question port
         let question_port = Arc::new(QuestionInMemoryRepository::default());
-        question_router_test(question_port).await;
+        let question_revision_port =
+            Arc::new(QuestionRevisionInMemoryRepository::new(&question_port));
+        let answer_port = Arc::new(AnswerInMemoryRepository::new(&question_port));
+        let tag_port = Arc::new(TagInMemoryRepository::new(&question_port));
+        question_router_test(question_port, question_revision_port, answer_port, tag_port).await;
     }
 }